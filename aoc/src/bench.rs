@@ -0,0 +1,121 @@
+//! Benchmarking each day's parse, part 1 and part 2, and comparing the timings against a saved
+//! baseline.
+//!
+//! Results are saved as a JSON list with one record per day and step, so a later run can be
+//! given the file as `--baseline`. A step is flagged as a regression when its median time grew
+//! by more than the threshold.
+use std::{collections::HashMap, fs, path::Path, time::Duration};
+
+use common::{
+    bench::Timing,
+    input::{self, InputError},
+    Solver,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BenchRecord {
+    day: u8,
+    step: String,
+    iterations: usize,
+    min_ns: u64,
+    median_ns: u64,
+    p95_ns: u64,
+}
+
+impl BenchRecord {
+    fn new(day: u8, timing: &Timing) -> Self {
+        let ns = |d: Duration| d.as_nanos() as u64;
+        BenchRecord {
+            day,
+            step: timing.step.to_string(),
+            iterations: timing.stats.iterations,
+            min_ns: ns(timing.stats.min),
+            median_ns: ns(timing.stats.median),
+            p95_ns: ns(timing.stats.p95),
+        }
+    }
+}
+
+pub struct BenchOptions<'a> {
+    pub iterations: usize,
+    pub save: Option<&'a Path>,
+    pub baseline: Option<&'a Path>,
+    /// how much slower (in percent) the median may get before it counts as a regression
+    pub threshold: f64,
+    /// skip days whose input is not downloaded yet instead of failing
+    pub skip_missing: bool,
+}
+
+fn load_baseline(path: &Path) -> Result<HashMap<(u8, String), BenchRecord>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let records: Vec<BenchRecord> =
+        serde_json::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(records
+        .into_iter()
+        .map(|r| ((r.day, r.step.clone()), r))
+        .collect())
+}
+
+fn ns(nanos: u64) -> Duration {
+    Duration::from_nanos(nanos)
+}
+
+pub fn bench(solvers: &[&dyn Solver], options: &BenchOptions) -> Result<(), String> {
+    let baseline = options.baseline.map(load_baseline).transpose()?;
+    let mut records = Vec::new();
+    let mut regressions = 0;
+
+    for solver in solvers {
+        let source = match input::resolve(solver.day(), None) {
+            Err(InputError::NotFound { .. }) if options.skip_missing => {
+                println!("day {:02} skipped, no input", solver.day());
+                continue;
+            }
+            source => source.map_err(|e| e.to_string())?,
+        };
+        let content = input::read(&source).map_err(|e| e.to_string())?;
+        let timings = solver
+            .bench(&content, options.iterations)
+            .map_err(|e| e.in_file(&source).to_string())?;
+
+        for timing in timings.iter() {
+            let record = BenchRecord::new(solver.day(), timing);
+            let mut line = format!(
+                "day {:02} {:<5}  min {:>12?}  median {:>12?}  p95 {:>12?}",
+                record.day,
+                record.step,
+                ns(record.min_ns),
+                ns(record.median_ns),
+                ns(record.p95_ns)
+            );
+
+            let base = baseline
+                .as_ref()
+                .and_then(|b| b.get(&(record.day, record.step.clone())));
+            if let Some(base) = base {
+                let change = (record.median_ns as f64 / base.median_ns.max(1) as f64 - 1.0) * 100.0;
+                line += &format!("  {:+7.1}%", change);
+                if change > options.threshold {
+                    regressions += 1;
+                    line += "  REGRESSION";
+                }
+            }
+            println!("{}", line);
+            records.push(record);
+        }
+    }
+
+    if let Some(path) = options.save {
+        let json = serde_json::to_string_pretty(&records).map_err(|e| e.to_string())?;
+        fs::write(path, json + "\n").map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+
+    match regressions {
+        0 => Ok(()),
+        n => Err(format!(
+            "{} step(s) got more than {}% slower than the baseline",
+            n, options.threshold
+        )),
+    }
+}
@@ -0,0 +1,328 @@
+//! Checking the answers against the ones recorded in `dayNN/answers.toml`.
+//!
+//! The answers file has one table per input file, named after the file in the day's directory:
+//!
+//! ```toml
+//! [input]
+//! part1 = 250898830
+//! part2 = 252127335
+//!
+//! [sample]
+//! part1 = 6440
+//! ```
+//!
+//! A part with no recorded answer is reported as unverified rather than passing. A day whose input
+//! has not been downloaded yet is reported as missing, which only fails the check if answers were
+//! recorded for it, and a day that fails to run is reported without stopping the other days from
+//! being checked.
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use common::{input, Answer, Part, Solver};
+
+const ANSWERS_FILE: &str = "answers.toml";
+
+/// the recorded answers for one input file
+#[derive(Debug, Default)]
+struct Expected {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl Expected {
+    fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+
+    fn is_recorded(&self) -> bool {
+        self.part1.is_some() || self.part2.is_some()
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Summary {
+    ok: usize,
+    mismatched: usize,
+    unverified: usize,
+    /// inputs that are not there, with no answers recorded for them
+    missing: usize,
+    /// inputs that are not there, although answers were recorded for them
+    unchecked: usize,
+    failed: usize,
+}
+
+impl Summary {
+    fn line(&self) -> String {
+        let mut line = format!(
+            "{} ok, {} mismatched, {} unverified",
+            self.ok, self.mismatched, self.unverified
+        );
+        for (count, what) in [
+            (self.missing, "missing"),
+            (self.unchecked, "unchecked"),
+            (self.failed, "failed"),
+        ] {
+            if count > 0 {
+                line += &format!(", {} {}", count, what);
+            }
+        }
+        line
+    }
+
+    /// fails if an answer changed, or if recorded answers could not be checked at all
+    fn outcome(&self) -> Result<(), String> {
+        match (self.mismatched, self.unchecked, self.failed) {
+            (0, 0, 0) => Ok(()),
+            (0, 0, n) => Err(format!("{} day(s) could not be checked", n)),
+            (0, n, _) => Err(format!("{} input(s) with recorded answers are missing", n)),
+            (n, _, _) => Err(format!("{} answer(s) did not match the recorded ones", n)),
+        }
+    }
+}
+
+fn answer_value(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(s) => Some(s.clone()),
+        toml::Value::Integer(i) => Some(i.to_string()),
+        _ => None,
+    }
+}
+
+fn load_answers(day: u8) -> Result<BTreeMap<String, Expected>, String> {
+    let path = input::day_dir(day).join(ANSWERS_FILE);
+    if !path.is_file() {
+        // nothing recorded yet, the real input is still checked so it shows up as unverified
+        return Ok(BTreeMap::from([("input".to_string(), Expected::default())]));
+    }
+
+    let content = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    parse_answers(&content, &path)
+}
+
+/// the answers recorded in `content`, read from `path`
+fn parse_answers(content: &str, path: &Path) -> Result<BTreeMap<String, Expected>, String> {
+    let mut answers = BTreeMap::new();
+    let table = content
+        .parse::<toml::Table>()
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    for (file, parts) in table.iter() {
+        let parts = parts
+            .as_table()
+            .ok_or_else(|| format!("{}: [{}] is not a table", path.display(), file))?;
+        let mut expected = Expected::default();
+        for (key, value) in parts.iter() {
+            let value = answer_value(value).ok_or_else(|| {
+                format!(
+                    "{}: {}.{} should be a string or an integer",
+                    path.display(),
+                    file,
+                    key
+                )
+            })?;
+            match key.as_str() {
+                "part1" => expected.part1 = Some(value),
+                "part2" => expected.part2 = Some(value),
+                _ => return Err(format!("{}: unknown key {}.{}", path.display(), file, key)),
+            }
+        }
+        answers.insert(file.clone(), expected);
+    }
+    Ok(answers)
+}
+
+/// where the input file is, or `None` if the real input has not been downloaded
+fn input_path(day: u8, file: &str) -> Option<PathBuf> {
+    // the real input follows the usual lookup, any other file lives next to it in the day's directory
+    match file {
+        "input" => match input::resolve(day, None).ok()? {
+            input::InputSource::File(path) => Some(path),
+            input::InputSource::Stdin => unreachable!("stdin is only used when asked for"),
+        },
+        _ => Some(input::day_dir(day).join(file)),
+    }
+}
+
+/// compares the answers for one input file with the recorded ones
+fn compare(day: u8, file: &str, expected: &Expected, answers: &[Answer], summary: &mut Summary) {
+    for answer in answers.iter() {
+        let label = format!("day {:02} {:<8} part {}", day, file, answer.part);
+        match expected.get(answer.part) {
+            Some(value) if value == answer.value => {
+                summary.ok += 1;
+                println!("{}: ok         {}", label, answer.value);
+            }
+            Some(value) => {
+                summary.mismatched += 1;
+                println!("{}: MISMATCH", label);
+                println!("  - {}", value);
+                println!("  + {}", answer.value);
+            }
+            None => {
+                summary.unverified += 1;
+                println!("{}: unverified {}", label, answer.value);
+            }
+        }
+    }
+}
+
+fn check_day(solver: &dyn Solver, summary: &mut Summary) -> Result<(), String> {
+    let day = solver.day();
+    for (file, expected) in load_answers(day)?.iter() {
+        let Some(path) = input_path(day, file) else {
+            match expected.is_recorded() {
+                true => {
+                    summary.unchecked += 1;
+                    println!(
+                        "day {:02} {:<8} MISSING, its recorded answers are not checked",
+                        day, file
+                    );
+                }
+                false => {
+                    summary.missing += 1;
+                    println!("day {:02} {:<8} missing, not checked", day, file);
+                }
+            }
+            continue;
+        };
+        let content =
+            fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let report = solver.solve(&content, &Part::BOTH).map_err(|e| {
+            e.in_file(&input::InputSource::File(path.clone()))
+                .to_string()
+        })?;
+        compare(day, file, expected, &report.answers, summary);
+    }
+    Ok(())
+}
+
+/// runs every given day against its recorded answers, failing if any answer changed
+pub fn check(solvers: &[&dyn Solver]) -> Result<(), String> {
+    let mut summary = Summary::default();
+    for solver in solvers {
+        if let Err(e) = check_day(*solver, &mut summary) {
+            summary.failed += 1;
+            println!("day {:02}: FAILED {}", solver.day(), e);
+        }
+    }
+
+    println!("{}", summary.line());
+    summary.outcome()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn answer(part: Part, value: &str) -> Answer {
+        Answer {
+            part,
+            value: value.to_string(),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn reads_the_recorded_answers() {
+        let path = Path::new("day07/answers.toml");
+        let answers = parse_answers(
+            "[input]\npart1 = 250898830\npart2 = \"252127335\"\n\n[sample]\npart1 = 6440\n",
+            path,
+        )
+        .unwrap();
+        assert_eq!(answers["input"].get(Part::One), Some("250898830"));
+        assert_eq!(answers["input"].get(Part::Two), Some("252127335"));
+        assert_eq!(answers["sample"].get(Part::Two), None);
+        assert!(answers["sample"].is_recorded());
+
+        let err = |content: &str| parse_answers(content, path).unwrap_err();
+        assert_eq!(
+            err("[input]\npart3 = 1\n"),
+            "day07/answers.toml: unknown key input.part3"
+        );
+        assert_eq!(
+            err("[input]\npart1 = 1.5\n"),
+            "day07/answers.toml: input.part1 should be a string or an integer"
+        );
+        assert_eq!(
+            err("input = 1\n"),
+            "day07/answers.toml: [input] is not a table"
+        );
+        assert!(err("[input\n").starts_with("day07/answers.toml: "));
+    }
+
+    #[test]
+    fn counts_matching_changed_and_unverified_answers() {
+        let expected = Expected {
+            part1: Some("142".to_string()),
+            part2: None,
+        };
+        let mut summary = Summary::default();
+        compare(
+            1,
+            "input",
+            &expected,
+            &[answer(Part::One, "142"), answer(Part::Two, "281")],
+            &mut summary,
+        );
+        compare(
+            1,
+            "sample",
+            &expected,
+            &[answer(Part::One, "143")],
+            &mut summary,
+        );
+        assert_eq!(
+            summary,
+            Summary {
+                ok: 1,
+                mismatched: 1,
+                unverified: 1,
+                ..Summary::default()
+            }
+        );
+        assert_eq!(summary.line(), "1 ok, 1 mismatched, 1 unverified");
+        assert!(summary.outcome().is_err());
+    }
+
+    #[test]
+    fn fails_when_recorded_answers_are_not_checked() {
+        let missing = Summary {
+            ok: 2,
+            unverified: 2,
+            missing: 1,
+            ..Summary::default()
+        };
+        assert_eq!(
+            missing.line(),
+            "2 ok, 0 mismatched, 2 unverified, 1 missing"
+        );
+        assert_eq!(missing.outcome(), Ok(()));
+
+        let unchecked = Summary {
+            unchecked: 1,
+            ..missing
+        };
+        assert_eq!(
+            unchecked.line(),
+            "2 ok, 0 mismatched, 2 unverified, 1 missing, 1 unchecked"
+        );
+        assert_eq!(
+            unchecked.outcome(),
+            Err("1 input(s) with recorded answers are missing".to_string())
+        );
+
+        let failed = Summary {
+            failed: 1,
+            ..Summary::default()
+        };
+        assert!(failed.outcome().is_err());
+    }
+}
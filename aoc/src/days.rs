@@ -0,0 +1,18 @@
+use common::Solver;
+
+/// every day that has a solution, in order
+pub const SOLVERS: &[&dyn Solver] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+];
+
+pub fn find(day: u8) -> Option<&'static dyn Solver> {
+    SOLVERS.iter().copied().find(|s| s.day() == day)
+}
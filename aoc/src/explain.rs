@@ -0,0 +1,90 @@
+//! `aoc explain`: how day 7 ranks each hand, with what its wild cards count as, as a table or CSV.
+use std::path::Path;
+
+use common::{input, Solution};
+use day07::{explain::Explanation, CamelRules, Day07};
+
+pub struct ExplainOptions<'a> {
+    /// the card labels from weakest to strongest
+    pub order: &'a str,
+    pub wild: &'a str,
+    pub hand_size: usize,
+    pub input: Option<&'a Path>,
+    pub csv: bool,
+}
+
+const HEADERS: [&str; 7] = [
+    "rank",
+    "line",
+    "hand",
+    "played as",
+    "type",
+    "bid",
+    "winnings",
+];
+
+fn fields(e: &Explanation) -> [String; 7] {
+    [
+        e.rank.to_string(),
+        e.line.to_string(),
+        e.hand.clone(),
+        e.played_as.clone(),
+        e.hand_type.to_string(),
+        e.bid.to_string(),
+        e.winnings.to_string(),
+    ]
+}
+
+/// quotes a field that would otherwise break the CSV
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
+
+fn print_csv(explanations: &[Explanation]) {
+    println!("{}", HEADERS.map(|h| h.replace(' ', "_")).join(","));
+    for e in explanations {
+        println!("{}", fields(e).map(|f| csv_field(&f)).join(","));
+    }
+}
+
+fn print_table(explanations: &[Explanation]) {
+    let rows = explanations.iter().map(fields).collect::<Vec<_>>();
+    let mut widths = HEADERS.map(str::len);
+    for row in &rows {
+        for (width, field) in widths.iter_mut().zip(row) {
+            *width = (*width).max(field.chars().count());
+        }
+    }
+    let line = |fields: &[&str]| {
+        let cells = fields
+            .iter()
+            .zip(widths)
+            .map(|(field, width)| format!("{:<width$}", field, width = width))
+            .collect::<Vec<_>>();
+        println!("{}", cells.join("  ").trim_end());
+    };
+    line(&HEADERS);
+    for row in &rows {
+        line(&row.each_ref().map(String::as_str));
+    }
+    let total = explanations.iter().map(|e| e.winnings).sum::<usize>();
+    println!("total winnings: {}", total);
+}
+
+pub fn explain(options: &ExplainOptions) -> Result<(), String> {
+    let rules = CamelRules::new(options.order, options.wild)
+        .and_then(|rules| rules.with_hand_size(options.hand_size))?;
+    let source = input::resolve(Day07::DAY, options.input).map_err(|e| e.to_string())?;
+    let content = input::read(&source).map_err(|e| e.to_string())?;
+    let bids = day07::parse_bids(&content, &rules).map_err(|e| e.in_file(&source).to_string())?;
+    let explanations = day07::explain::explain(&bids, &rules);
+
+    match options.csv {
+        true => print_csv(&explanations),
+        false => print_table(&explanations),
+    }
+    Ok(())
+}
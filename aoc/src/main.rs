@@ -0,0 +1,300 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::{Args, Parser, Subcommand};
+use common::{
+    input::{self, InputError},
+    Part, Report, Solver,
+};
+
+mod bench;
+mod check;
+mod days;
+mod explain;
+mod scaffold;
+mod trace;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent-of-Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solver for one day (or every day) and print the answers
+    Run(RunArgs),
+    /// Compare the answers against the ones recorded in each day's `answers.toml`
+    Check(CheckArgs),
+    /// Time each day's parse, part 1 and part 2 over many runs
+    Bench(BenchArgs),
+    /// Create the crate for a new day, wired into the workspace and this runner
+    New(NewArgs),
+    /// Follow a value or range through day 5's almanac, printing it in every category on the way
+    Trace(TraceArgs),
+    /// Show how day 7 ranks each hand and what its wild cards count as
+    Explain(ExplainArgs),
+}
+
+#[derive(Args)]
+struct DaySelection {
+    /// The day to run
+    #[arg(
+        required_unless_present = "all",
+        conflicts_with = "all",
+        value_parser = clap::value_parser!(u8).range(1..=25)
+    )]
+    day: Option<u8>,
+
+    /// Run every day that has a solution
+    #[arg(long)]
+    all: bool,
+}
+
+impl DaySelection {
+    fn solvers(&self) -> Result<Vec<&'static dyn Solver>, String> {
+        match self.day {
+            Some(day) => days::find(day)
+                .map(|s| vec![s])
+                .ok_or_else(|| format!("no solution for day {}", day)),
+            None => Ok(days::SOLVERS.to_vec()),
+        }
+    }
+}
+
+#[derive(Args)]
+struct RunArgs {
+    #[command(flatten)]
+    days: DaySelection,
+
+    /// Only run this part
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Read the input from this file instead of looking it up (`-` reads stdin)
+    #[arg(long, conflicts_with = "all")]
+    input: Option<PathBuf>,
+}
+
+impl RunArgs {
+    fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(1) => vec![Part::One],
+            Some(_) => vec![Part::Two],
+            None => Part::BOTH.to_vec(),
+        }
+    }
+}
+
+#[derive(Args)]
+struct CheckArgs {
+    #[command(flatten)]
+    days: DaySelection,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[command(flatten)]
+    days: DaySelection,
+
+    /// How many times to run each step
+    #[arg(long, short = 'n', default_value_t = 20)]
+    iterations: usize,
+
+    /// Write the results as JSON to this file
+    #[arg(long)]
+    save: Option<PathBuf>,
+
+    /// Compare against results saved by an earlier run
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// Flag steps whose median got slower than the baseline by more than this percentage
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    /// The day to create
+    day: u8,
+
+    /// Download the input and puzzle description (runs `$AOC_FETCH_CMD`, `aoc-cli` by default)
+    #[arg(long)]
+    fetch: bool,
+
+    /// Commit the new crate to git
+    #[arg(long)]
+    commit: bool,
+}
+
+#[derive(Args)]
+struct TraceArgs {
+    /// The category the values belong to, such as `seed`
+    category: String,
+
+    /// A single value (`79`) or a half-open range (`79..93`)
+    values: String,
+
+    /// The category to follow the values to
+    #[arg(long, default_value = "location")]
+    to: String,
+
+    /// Read the almanac from this file instead of looking it up (`-` reads stdin)
+    #[arg(long)]
+    input: Option<PathBuf>,
+
+    /// Print the trace as JSON instead of a table
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+struct ExplainArgs {
+    /// The card labels from weakest to strongest
+    #[arg(long, default_value = "J23456789TQKA")]
+    order: String,
+
+    /// The card labels that are wild (`--wild ''` for part 1's rules)
+    #[arg(long, default_value = "J")]
+    wild: String,
+
+    /// How many cards make a hand
+    #[arg(long, default_value_t = 5)]
+    hand_size: usize,
+
+    /// Read the bids from this file instead of looking them up (`-` reads stdin)
+    #[arg(long)]
+    input: Option<PathBuf>,
+
+    /// Print CSV instead of a table
+    #[arg(long)]
+    csv: bool,
+}
+
+fn print_report(report: &Report) {
+    println!("day {:02} (parse {:?})", report.day, report.parse_time);
+    for answer in report.answers.iter() {
+        println!(
+            "  part {}: {:<20} ({:?})",
+            answer.part, answer.value, answer.elapsed
+        );
+    }
+}
+
+fn run(args: &RunArgs) -> Result<(), String> {
+    let parts = args.parts();
+    for solver in args.days.solvers()? {
+        let source = match input::resolve(solver.day(), args.input.as_deref()) {
+            // a day that was just created has no input yet, which should not stop the others
+            Err(InputError::NotFound { .. }) if args.days.all => {
+                println!("day {:02} skipped, no input", solver.day());
+                continue;
+            }
+            source => source.map_err(|e| e.to_string())?,
+        };
+        let input = input::read(&source).map_err(|e| e.to_string())?;
+        let report = solver
+            .solve(&input, &parts)
+            .map_err(|e| e.in_file(&source).to_string())?;
+        print_report(&report);
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let res = match cli.command {
+        Command::Run(args) => run(&args),
+        Command::Check(args) => args.days.solvers().and_then(|s| check::check(&s)),
+        Command::Bench(args) => args.days.solvers().and_then(|s| {
+            let options = bench::BenchOptions {
+                iterations: args.iterations,
+                save: args.save.as_deref(),
+                baseline: args.baseline.as_deref(),
+                threshold: args.threshold,
+                skip_missing: args.days.all,
+            };
+            bench::bench(&s, &options)
+        }),
+        Command::New(args) => scaffold::new_day(
+            &input::workspace_root(),
+            args.day,
+            &scaffold::NewOptions {
+                fetch: args.fetch,
+                commit: args.commit,
+            },
+        ),
+        Command::Trace(args) => trace::trace(
+            &args.category,
+            &args.values,
+            &trace::TraceOptions {
+                to: &args.to,
+                input: args.input.as_deref(),
+                json: args.json,
+            },
+        ),
+        Command::Explain(args) => explain::explain(&explain::ExplainOptions {
+            order: &args.order,
+            wild: &args.wild,
+            hand_size: args.hand_size,
+            input: args.input.as_deref(),
+            csv: args.csv,
+        }),
+    };
+
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selection(args: &[&str]) -> Result<DaySelection, clap::Error> {
+        let args = ["aoc", "check"].iter().chain(args);
+        match Cli::try_parse_from(args)?.command {
+            Command::Check(args) => Ok(args.days),
+            _ => unreachable!("the command is always check"),
+        }
+    }
+
+    fn chosen_days(selection: &DaySelection) -> Result<Vec<u8>, String> {
+        Ok(selection.solvers()?.iter().map(|s| s.day()).collect())
+    }
+
+    #[test]
+    fn selects_one_day_or_all_of_them() {
+        assert_eq!(chosen_days(&selection(&["5"]).unwrap()), Ok(vec![5]));
+        let all = selection(&["--all"]).unwrap();
+        let every_day = days::SOLVERS.iter().map(|s| s.day()).collect::<Vec<_>>();
+        assert_eq!(chosen_days(&all), Ok(every_day));
+
+        assert!(selection(&[]).is_err());
+        assert!(selection(&["5", "--all"]).is_err());
+        assert!(selection(&["five"]).is_err());
+    }
+
+    #[test]
+    fn rejects_days_without_a_solution() {
+        for day in ["0", "26", "300"] {
+            let err = selection(&[day]).err().unwrap();
+            assert_eq!(
+                err.kind(),
+                clap::error::ErrorKind::ValueValidation,
+                "{}",
+                day
+            );
+        }
+        let unsolved = selection(&["25"]).unwrap();
+        assert_eq!(
+            chosen_days(&unsolved),
+            Err("no solution for day 25".to_string())
+        );
+    }
+}
@@ -0,0 +1,338 @@
+//! `aoc new <day>`: creating a day crate that is already wired into the workspace and runner.
+//!
+//! Every step checks what is already there first, so running it again for an existing day only
+//! fills in whatever is missing.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use common::{puzzle::Puzzle, Part};
+use toml_edit::{DocumentMut, InlineTable, Item, Value};
+
+const FETCH_CMD_VAR: &str = "AOC_FETCH_CMD";
+/// the binary the aoc-cli crate installs
+const DEFAULT_FETCH_CMD: &str = "aoc";
+const SOLVERS_END: &str = "];";
+
+pub struct NewOptions {
+    /// download the input and puzzle description with the external download tool
+    pub fetch: bool,
+    /// commit the new crate to git
+    pub commit: bool,
+}
+
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{{DD}}", &format!("{:02}", day))
+        .replace("{{D}}", &day.to_string())
+}
+
+/// writes a file unless it already exists, returning whether it was written
+fn write_new(path: &Path, content: &str) -> Result<bool, String> {
+    if path.exists() {
+        return Ok(false);
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+    fs::write(path, content).map_err(|e| format!("{}: {}", path.display(), e))?;
+    println!("created {}", path.display());
+    Ok(true)
+}
+
+fn edit_file(path: &Path, edit: impl Fn(&str) -> Result<String, String>) -> Result<(), String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let edited = edit(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+    if edited != content {
+        fs::write(path, edited).map_err(|e| format!("{}: {}", path.display(), e))?;
+        println!("updated {}", path.display());
+    }
+    Ok(())
+}
+
+/// adds a crate to `workspace.members`, keeping the list sorted
+fn add_member(manifest: &str, name: &str) -> Result<String, String> {
+    let mut doc = manifest.parse::<DocumentMut>().map_err(|e| e.to_string())?;
+    let members = doc["workspace"]["members"]
+        .as_array_mut()
+        .ok_or("workspace.members is not an array")?;
+    if members.iter().any(|m| m.as_str() == Some(name)) {
+        return Ok(manifest.to_string());
+    }
+
+    let position = members
+        .iter()
+        .position(|m| m.as_str().is_some_and(|m| m > name))
+        .unwrap_or(members.len());
+    members.insert(position, name);
+    // match the one-member-per-line layout of the other entries
+    if let Some(member) = members.get_mut(position) {
+        member.decor_mut().set_prefix("\n");
+    }
+    Ok(doc.to_string())
+}
+
+/// adds a path dependency on a day crate to the runner's manifest
+fn add_dependency(manifest: &str, name: &str) -> Result<String, String> {
+    let mut doc = manifest.parse::<DocumentMut>().map_err(|e| e.to_string())?;
+    let deps = doc["dependencies"]
+        .as_table_mut()
+        .ok_or("dependencies is not a table")?;
+    if deps.contains_key(name) {
+        return Ok(manifest.to_string());
+    }
+
+    let mut dep = InlineTable::new();
+    dep.insert("version", "0.1.0".into());
+    dep.insert("path", format!("../{}", name).into());
+    deps.insert(name, Item::Value(Value::InlineTable(dep)));
+    deps.sort_values();
+    Ok(doc.to_string())
+}
+
+/// adds the day to the runner's list of solvers
+fn add_solver(days: &str, day: u8) -> Result<String, String> {
+    let entry = format!("&day{:02}::Day{:02},", day, day);
+    if days.contains(&entry) {
+        return Ok(days.to_string());
+    }
+
+    let mut lines = days.lines().map(String::from).collect::<Vec<_>>();
+    let end = lines
+        .iter()
+        .position(|l| l.trim() == SOLVERS_END)
+        .ok_or("could not find the end of the SOLVERS list")?;
+    // keep the list in day order
+    let position = lines[..end]
+        .iter()
+        .position(|l| l.trim().starts_with("&day") && l.trim() > entry.as_str())
+        .unwrap_or(end);
+    lines.insert(position, format!("    {}", entry));
+    Ok(lines.join("\n") + "\n")
+}
+
+fn fetch(day_dir: &Path, day: u8) -> Result<(), String> {
+    let cmd = env::var(FETCH_CMD_VAR).unwrap_or_else(|_| DEFAULT_FETCH_CMD.to_string());
+    // the download tool writes `input` and `puzzle.md` to the current directory
+    let status = Command::new(&cmd)
+        .args(["-d", &day.to_string(), "download"])
+        .current_dir(day_dir)
+        .status()
+        .map_err(|e| {
+            format!(
+                "could not run {} (set {} to change it): {}",
+                cmd, FETCH_CMD_VAR, e
+            )
+        })?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(format!("{} failed: {}", cmd, status)),
+    }
+}
+
+/// The example tests for the parts `puzzle.md` has an example for, ignored until the parts are
+/// solved, or `None` if it has none at all.
+fn example_tests(puzzle: &str, day: u8) -> Option<String> {
+    let puzzle = Puzzle::parse(puzzle);
+    let parts = Part::BOTH
+        .iter()
+        .filter(|&&part| puzzle.example(part).is_some())
+        .map(|part| {
+            format!(
+                "    #[ignore = \"part {} is not solved yet\"]\n    part{},\n",
+                part, part
+            )
+        })
+        .collect::<String>();
+    match parts.is_empty() {
+        true => None,
+        false => Some(
+            render(include_str!("../templates/examples.rs.tmpl"), day).replace("{{PARTS}}", &parts),
+        ),
+    }
+}
+
+fn commit(root: &Path, crate_name: &str) -> Result<(), String> {
+    let git = |args: &[&str]| -> Result<(), String> {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .status()
+            .map_err(|e| format!("could not run git: {}", e))?;
+        match status.success() {
+            true => Ok(()),
+            false => Err(format!("git {} failed: {}", args.join(" "), status)),
+        }
+    };
+    // only what this command created or edited, not whatever else is going on in the tree
+    git(&[
+        "add",
+        "Cargo.toml",
+        "aoc/Cargo.toml",
+        "aoc/src/days.rs",
+        crate_name,
+    ])?;
+    git(&["commit", "-m", &format!("feat: adding {}", crate_name)])
+}
+
+/// creates the day's crate in the workspace at `root`
+pub fn new_day(root: &Path, day: u8, options: &NewOptions) -> Result<(), String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("day {} is not an Advent-of-Code day", day));
+    }
+    let crate_name = format!("day{:02}", day);
+    let day_dir = root.join(&crate_name);
+
+    let templates: [(PathBuf, &str); 3] = [
+        (
+            day_dir.join("Cargo.toml"),
+            include_str!("../templates/Cargo.toml.tmpl"),
+        ),
+        (
+            day_dir.join("src/lib.rs"),
+            include_str!("../templates/lib.rs.tmpl"),
+        ),
+        (
+            day_dir.join("src/main.rs"),
+            include_str!("../templates/main.rs.tmpl"),
+        ),
+    ];
+    for (path, template) in templates.iter() {
+        write_new(path, &render(template, day))?;
+    }
+
+    edit_file(&root.join("Cargo.toml"), |m| add_member(m, &crate_name))?;
+    edit_file(&root.join("aoc/Cargo.toml"), |m| {
+        add_dependency(m, &crate_name)
+    })?;
+    edit_file(&root.join("aoc/src/days.rs"), |d| add_solver(d, day))?;
+
+    if options.fetch {
+        fetch(&day_dir, day)?;
+    }
+
+    // the worked examples can only be tested once the puzzle description is there
+    let examples = fs::read_to_string(day_dir.join("puzzle.md"))
+        .ok()
+        .and_then(|puzzle| example_tests(&puzzle, day))
+        .unwrap_or_else(|| render(include_str!("../templates/examples_no_puzzle.rs.tmpl"), day));
+    write_new(&day_dir.join("tests/examples.rs"), &examples)?;
+
+    if options.commit {
+        commit(root, &crate_name)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKSPACE: &str =
+        "[workspace]\nresolver = \"2\"\n\nmembers = [\n\"aoc\",\n\"day01\",\n\"day03\",\n]\n";
+
+    #[test]
+    fn adding_a_member_is_sorted_and_idempotent() {
+        let added = add_member(WORKSPACE, "day02").unwrap();
+        assert_eq!(
+            added,
+            "[workspace]\nresolver = \"2\"\n\nmembers = [\n\"aoc\",\n\"day01\",\n\"day02\",\n\"day03\",\n]\n"
+        );
+        assert_eq!(add_member(&added, "day02").unwrap(), added);
+    }
+
+    #[test]
+    fn adding_a_solver_is_idempotent() {
+        let days =
+            "pub const SOLVERS: &[&dyn Solver] = &[\n    &day01::Day01,\n    &day03::Day03,\n];\n";
+        let added = add_solver(days, 2).unwrap();
+        assert_eq!(
+            added,
+            "pub const SOLVERS: &[&dyn Solver] = &[\n    &day01::Day01,\n    &day02::Day02,\n    &day03::Day03,\n];\n"
+        );
+        assert_eq!(add_solver(&added, 2).unwrap(), added);
+    }
+
+    fn copy_dir(from: &Path, to: &Path) {
+        fs::create_dir_all(to).unwrap();
+        for entry in fs::read_dir(from).unwrap() {
+            let entry = entry.unwrap();
+            let path = to.join(entry.file_name());
+            if entry.file_type().unwrap().is_dir() {
+                copy_dir(&entry.path(), &path);
+            } else {
+                fs::copy(entry.path(), path).unwrap();
+            }
+        }
+    }
+
+    /// scaffolds a day in a copy of the parts of the workspace it touches, and builds and tests it
+    #[test]
+    fn creates_a_day_that_builds_and_is_registered() {
+        let root = env::temp_dir().join(format!("aoc-new-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        copy_dir(
+            &common::input::workspace_root().join("common"),
+            &root.join("common"),
+        );
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nresolver = \"2\"\n\nmembers = [\n\"common\",\n]\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(
+            root.join("aoc/Cargo.toml"),
+            "[package]\nname = \"aoc\"\n\n[dependencies]\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("aoc/src/days.rs"),
+            "pub const SOLVERS: &[&dyn Solver] = &[\n];\n",
+        )
+        .unwrap();
+        // a puzzle that only has its first part so far
+        fs::create_dir_all(root.join("day10")).unwrap();
+        fs::write(
+            root.join("day10/puzzle.md"),
+            "For example:\n\n```\n1\n2\n```\n\nThat makes `*3*`.\n",
+        )
+        .unwrap();
+
+        let options = NewOptions {
+            fetch: false,
+            commit: false,
+        };
+        new_day(&root, 10, &options).unwrap();
+
+        let read = |path: &str| fs::read_to_string(root.join(path)).unwrap();
+        assert!(read("Cargo.toml").contains("\"day10\""));
+        assert!(
+            read("aoc/Cargo.toml").contains("day10 = { version = \"0.1.0\", path = \"../day10\" }")
+        );
+        assert!(read("aoc/src/days.rs").contains("&day10::Day10,"));
+        let examples = read("day10/tests/examples.rs");
+        assert!(examples.contains("#[ignore = \"part 1 is not solved yet\"]\n    part1,"));
+        assert!(!examples.contains("part2"));
+
+        // running it again changes nothing
+        let before = read("day10/src/lib.rs");
+        new_day(&root, 10, &options).unwrap();
+        assert_eq!(read("day10/src/lib.rs"), before);
+
+        let output = Command::new(env!("CARGO"))
+            .args(["test", "--offline", "-p", "day10"])
+            .current_dir(&root)
+            .env("CARGO_TARGET_DIR", root.join("target"))
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
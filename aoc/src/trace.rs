@@ -0,0 +1,160 @@
+//! `aoc trace`: following a value or a range of values through day 5's almanac, showing what it
+//! maps to in every category on the way.
+use std::path::Path;
+
+use common::{
+    input,
+    interval::{Interval, IntervalSet},
+    Solution,
+};
+use day05::{Day05, Stage};
+use serde::Serialize;
+
+pub struct TraceOptions<'a> {
+    /// the category to stop at
+    pub to: &'a str,
+    pub input: Option<&'a Path>,
+    pub json: bool,
+}
+
+/// a half-open interval, `end` being excluded
+#[derive(Debug, Serialize)]
+struct IntervalRecord {
+    start: usize,
+    end: usize,
+}
+
+impl From<Interval> for IntervalRecord {
+    fn from(i: Interval) -> Self {
+        IntervalRecord {
+            start: i.start,
+            end: i.end,
+        }
+    }
+}
+
+/// a piece of the values, and the piece of the previous category it was mapped from
+#[derive(Debug, Serialize)]
+struct PieceRecord {
+    source: IntervalRecord,
+    dest: IntervalRecord,
+}
+
+#[derive(Debug, Serialize)]
+struct StageRecord<'a> {
+    category: &'a str,
+    pieces: Vec<PieceRecord>,
+}
+
+/// a single value such as `79`, or a half-open range such as `79..93`
+fn parse_values(values: &str) -> Result<Interval, String> {
+    let number = |n: &str| {
+        n.trim()
+            .parse::<usize>()
+            .map_err(|e| format!("invalid value {:?}: {}", n, e))
+    };
+    match values.split_once("..") {
+        Some((start, end)) => {
+            let (start, end) = (number(start)?, number(end)?);
+            match start < end {
+                true => Ok(Interval::new(start, end)),
+                false => Err(format!("the range {} is empty", values)),
+            }
+        }
+        None => {
+            let value = number(values)?;
+            Interval::checked_with_len(value, 1)
+                .ok_or_else(|| format!("{} is too large to trace, use a range instead", value))
+        }
+    }
+}
+
+fn format_interval(i: Interval) -> String {
+    match i.len() {
+        1 => i.start.to_string(),
+        _ => i.to_string(),
+    }
+}
+
+/// the pieces of the first stage on their own, the others along with where they came from
+fn format_pieces(stage: &Stage, first: bool) -> String {
+    let pieces = stage
+        .pieces
+        .iter()
+        .map(|p| match first {
+            true => format_interval(p.dest_interval()),
+            false => format!(
+                "{} -> {}",
+                format_interval(p.source),
+                format_interval(p.dest_interval())
+            ),
+        })
+        .collect::<Vec<_>>();
+    match pieces.is_empty() {
+        true => "-".to_string(),
+        false => pieces.join(", "),
+    }
+}
+
+fn print_table(stages: &[Stage]) {
+    let width = stages.iter().map(|s| s.category.len()).max().unwrap_or(0);
+    for (i, stage) in stages.iter().enumerate() {
+        println!(
+            "{:<width$}  {}",
+            stage.category,
+            format_pieces(stage, i == 0),
+            width = width
+        );
+    }
+}
+
+fn print_json(stages: &[Stage]) -> Result<(), String> {
+    let records = stages
+        .iter()
+        .map(|s| StageRecord {
+            category: &s.category,
+            pieces: s
+                .pieces
+                .iter()
+                .map(|p| PieceRecord {
+                    source: p.source.into(),
+                    dest: p.dest_interval().into(),
+                })
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+    let json = serde_json::to_string_pretty(&records).map_err(|e| e.to_string())?;
+    println!("{}", json);
+    Ok(())
+}
+
+pub fn trace(from: &str, values: &str, options: &TraceOptions) -> Result<(), String> {
+    let values = IntervalSet::from_iter([parse_values(values)?]);
+    let source = input::resolve(Day05::DAY, options.input).map_err(|e| e.to_string())?;
+    let content = input::read(&source).map_err(|e| e.to_string())?;
+    let almanac = Day05::parse(&content).map_err(|e| e.in_file(&source).to_string())?;
+    let stages = almanac
+        .trace(values, from, options.to)
+        .map_err(|e| e.to_string())?;
+
+    match options.json {
+        true => print_json(&stages),
+        false => {
+            print_table(&stages);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_values_and_ranges() {
+        assert_eq!(parse_values("79"), Ok(Interval::new(79, 80)));
+        assert_eq!(parse_values("79..93"), Ok(Interval::new(79, 93)));
+        assert!(parse_values("93..79").is_err());
+        assert!(parse_values("18446744073709551615").is_err());
+    }
+}
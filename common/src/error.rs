@@ -0,0 +1,138 @@
+use std::{fmt, path::PathBuf};
+
+use crate::input::{InputError, InputSource};
+
+/// An error in a puzzle input, pointing at where in the input it happened.
+///
+/// Parsers only know about the text they are given, so they fill in the line and column and the
+/// harness that read the file adds its name with [`AocError::in_file`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AocError {
+    pub file: Option<PathBuf>,
+    /// 1-based line number
+    pub line: Option<usize>,
+    /// 1-based column number, in characters
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+impl AocError {
+    pub fn new(message: impl Into<String>) -> Self {
+        AocError {
+            file: None,
+            line: None,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    /// an error about a whole line, `line_idx` is the 0-based index as given by `enumerate()`
+    pub fn at_line(line_idx: usize, message: impl Into<String>) -> Self {
+        AocError {
+            line: Some(line_idx + 1),
+            ..AocError::new(message)
+        }
+    }
+
+    /// an error about `token`, which has to be a slice of `line`, so its column can be found
+    pub fn at(line_idx: usize, line: &str, token: &str, message: impl Into<String>) -> Self {
+        AocError {
+            column: column_of(line, token),
+            ..AocError::at_line(line_idx, message)
+        }
+    }
+
+    /// adds the input file the error came from, unless it is already known
+    pub fn in_file(mut self, file: &InputSource) -> Self {
+        if self.file.is_none() {
+            self.file = Some(PathBuf::from(file.to_string()));
+        }
+        self
+    }
+
+    /// shifts the line number, for errors from a parser that was given a later part of the input
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        self.line = self.line.map(|line| line + lines);
+        self
+    }
+}
+
+/// the 1-based column of `token` within `line`, if it is a slice of it
+fn column_of(line: &str, token: &str) -> Option<usize> {
+    let offset = (token.as_ptr() as usize).checked_sub(line.as_ptr() as usize)?;
+    if offset > line.len() {
+        return None;
+    }
+    Some(line[..offset].chars().count() + 1)
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{}:", line)?;
+        }
+        if let Some(column) = self.column {
+            write!(f, "{}:", column)?;
+        }
+        if self.file.is_some() || self.line.is_some() {
+            write!(f, " ")?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for AocError {}
+
+impl From<InputError> for AocError {
+    fn from(err: InputError) -> Self {
+        AocError::new(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_column_of_a_token() {
+        let line = "Game 1: 3 blué, 4 red";
+        let count = &line[16..17];
+        assert_eq!(column_of(line, count), Some(16));
+        assert_eq!(column_of(line, &line[line.len()..]), Some(22));
+        // a copy of the token is not part of the line
+        let copy = count.to_string();
+        assert_eq!(column_of(line, &copy), None);
+        assert_eq!(column_of(&line[8..], line), None);
+    }
+
+    #[test]
+    fn shows_what_is_known_about_the_location() {
+        let line = "seeds: 79 x";
+        let err = AocError::at(2, line, &line[10..], "not a number");
+        assert_eq!(err.to_string(), "3:11: not a number");
+        assert_eq!(
+            AocError::at_line(0, "empty line").to_string(),
+            "1: empty line"
+        );
+        assert_eq!(AocError::new("no input").to_string(), "no input");
+        assert_eq!(
+            AocError::at_line(1, "bad").offset_lines(3).to_string(),
+            "5: bad"
+        );
+    }
+
+    #[test]
+    fn keeps_the_first_file() {
+        let err = AocError::at_line(4, "bad").in_file(&InputSource::File("day02/input".into()));
+        assert_eq!(err.to_string(), "day02/input:5: bad");
+        let err = err.in_file(&InputSource::Stdin);
+        assert_eq!(err.to_string(), "day02/input:5: bad");
+        let err = AocError::new("bad").in_file(&InputSource::Stdin);
+        assert_eq!(err.to_string(), "<stdin>: bad");
+    }
+}
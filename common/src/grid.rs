@@ -0,0 +1,222 @@
+//! A rectangular grid of cells, sized by the input it was read from.
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::AocError;
+
+/// a position in a grid, `x` is the column and `y` the row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Coord {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Coord {
+    pub fn new(x: usize, y: usize) -> Self {
+        Coord { x, y }
+    }
+}
+
+const NEIGHBORS_4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const NEIGHBORS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl Grid<char> {
+    /// reads a grid of characters, one row per line
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        Grid::parse_with(input, Ok)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Reads a grid one row per line, converting each character with `f`. Every row has to be as
+    /// wide as the first one.
+    pub fn parse_with<F>(input: &str, mut f: F) -> Result<Self, AocError>
+    where
+        F: FnMut(char) -> Result<T, String>,
+    {
+        let mut width = None;
+        let mut cells = Vec::new();
+        let mut height = 0;
+        for (idx, line) in input.lines().enumerate() {
+            let row_start = cells.len();
+            for (i, c) in line.char_indices() {
+                cells.push(f(c).map_err(|e| AocError::at(idx, line, &line[i..], e))?);
+            }
+
+            let row_width = cells.len() - row_start;
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(AocError::at_line(
+                        idx,
+                        format!("expected a row of {} cells, found {}", width, row_width),
+                    ))
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len(), "cells do not fill the grid");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        coord.x < self.width && coord.y < self.height
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        match self.contains(coord) {
+            true => self.cells.get(coord.y * self.width + coord.x),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        match self.contains(coord) {
+            true => self.cells.get_mut(coord.y * self.width + coord.x),
+            false => None,
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero width, which only happens for a grid without cells
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// every cell along with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| (Coord::new(idx % width, idx / width), cell))
+    }
+
+    fn offsets<'a>(
+        &self,
+        coord: Coord,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Coord> + 'a {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let x = coord.x.checked_add_signed(dx)?;
+            let y = coord.y.checked_add_signed(dy)?;
+            (x < width && y < height).then_some(Coord::new(x, y))
+        })
+    }
+
+    /// the neighbours above, left, right and below a cell that are inside the grid
+    pub fn neighbors4(&self, coord: Coord) -> impl Iterator<Item = Coord> {
+        self.offsets(coord, &NEIGHBORS_4)
+    }
+
+    /// the neighbours of a cell that are inside the grid, including the diagonal ones
+    pub fn neighbors8(&self, coord: Coord) -> impl Iterator<Item = Coord> {
+        self.offsets(coord, &NEIGHBORS_8)
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &T {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", coord))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", coord))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbors_stay_inside_the_grid() {
+        let grid = Grid::parse("abc\ndef\n").unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[Coord::new(2, 1)], 'f');
+        assert_eq!(grid.get(Coord::new(3, 0)), None);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+
+        let corner = grid.neighbors8(Coord::new(0, 0)).collect::<Vec<_>>();
+        assert_eq!(
+            corner,
+            vec![Coord::new(1, 0), Coord::new(0, 1), Coord::new(1, 1)]
+        );
+        assert_eq!(grid.neighbors4(Coord::new(1, 1)).count(), 3);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        let err = Grid::parse("abc\nde\n").unwrap_err();
+        assert_eq!(err.line, Some(2));
+    }
+}
@@ -0,0 +1,246 @@
+//! Locating and reading a day's puzzle input.
+//!
+//! The input is looked up in this order:
+//! 1. an explicit path (for example from `--input`), where `-` means stdin
+//! 2. the directory in the `AOC_INPUT_DIR` environment variable
+//! 3. the day's crate directory in the workspace root
+use std::{
+    env, fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// where a day's input was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// none of the candidate paths exist
+    NotFound { day: u8, tried: Vec<PathBuf> },
+    /// the input was found but could not be read
+    Read { source: InputSource, err: io::Error },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::NotFound { day, tried } => {
+                write!(f, "could not find the input for day {}, tried:", day)?;
+                for path in tried {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                Ok(())
+            }
+            InputError::Read { source, err } => write!(f, "could not read {}: {}", source, err),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// the workspace root, which is the parent of this crate's manifest directory
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("common is a workspace member")
+        .to_path_buf()
+}
+
+pub fn day_dir(day: u8) -> PathBuf {
+    workspace_root().join(format!("day{:02}", day))
+}
+
+/// the paths that are checked for a day's input, in order of preference
+pub fn candidates(day: u8, explicit: Option<&Path>) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(path) = explicit {
+        paths.push(path.to_path_buf());
+    }
+    if let Some(dir) = env::var_os(INPUT_DIR_VAR) {
+        let dir = PathBuf::from(dir);
+        // allow both a flat directory of `dayNN` files and one directory per day
+        paths.push(dir.join(format!("day{:02}", day)));
+        paths.push(dir.join(format!("day{:02}", day)).join("input"));
+    }
+    paths.push(day_dir(day).join("input"));
+    paths
+}
+
+/// finds a day's input without reading it
+pub fn resolve(day: u8, explicit: Option<&Path>) -> Result<InputSource, InputError> {
+    if explicit == Some(Path::new("-")) {
+        return Ok(InputSource::Stdin);
+    }
+
+    let tried = candidates(day, explicit);
+    // an explicit path is never silently replaced by a fallback
+    let usable = match explicit {
+        Some(_) => &tried[..1],
+        None => &tried[..],
+    };
+    match usable.iter().find(|path| path.is_file()) {
+        Some(path) => Ok(InputSource::File(path.clone())),
+        None => Err(InputError::NotFound {
+            day,
+            tried: usable.to_vec(),
+        }),
+    }
+}
+
+pub fn read(source: &InputSource) -> Result<String, InputError> {
+    let res = match source {
+        InputSource::Stdin => {
+            let mut buf = String::new();
+            io::stdin().read_to_string(&mut buf).map(|_| buf)
+        }
+        InputSource::File(path) => fs::read_to_string(path),
+    };
+    res.map_err(|err| InputError::Read {
+        source: source.clone(),
+        err,
+    })
+}
+
+/// finds and reads a day's input
+pub fn load(day: u8, explicit: Option<&Path>) -> Result<String, InputError> {
+    read(&resolve(day, explicit)?)
+}
+
+/// finds a day's input for a day binary, taking an optional `--input <path>` from the command line
+pub fn source_from_args(day: u8) -> Result<InputSource, InputError> {
+    let mut args = env::args().skip(1);
+    let mut explicit = None;
+    while let Some(arg) = args.next() {
+        if arg == "--input" {
+            explicit = args.next().map(PathBuf::from);
+        } else if let Some(path) = arg.strip_prefix("--input=") {
+            explicit = Some(PathBuf::from(path));
+        }
+    }
+    resolve(day, explicit.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// an empty directory of its own for each test
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-input-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn an_explicit_path_is_never_replaced() {
+        let dir = temp_dir("explicit");
+        let path = dir.join("example.txt");
+        fs::write(&path, "1abc2\n").unwrap();
+
+        assert_eq!(
+            resolve(1, Some(Path::new("-"))).unwrap(),
+            InputSource::Stdin
+        );
+        assert_eq!(
+            resolve(1, Some(&path)).unwrap(),
+            InputSource::File(path.clone())
+        );
+        assert_eq!(load(1, Some(&path)).unwrap(), "1abc2\n");
+
+        // day 1 has an input in the workspace, but it is not used instead of a missing file
+        let missing = dir.join("missing.txt");
+        match resolve(1, Some(&missing)) {
+            Err(InputError::NotFound { day: 1, tried }) => assert_eq!(tried, [missing]),
+            res => panic!("expected NotFound, got {:?}", res),
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// the only test that touches `AOC_INPUT_DIR`, so tests running in parallel do not see it
+    #[test]
+    fn looks_in_the_input_dir_before_the_workspace() {
+        let dir = temp_dir("env");
+        let workspace_input = day_dir(1).join("input");
+
+        env::remove_var(INPUT_DIR_VAR);
+        assert_eq!(
+            resolve(1, None).unwrap(),
+            InputSource::File(workspace_input.clone())
+        );
+
+        // an empty input dir falls back to the workspace
+        env::set_var(INPUT_DIR_VAR, &dir);
+        assert_eq!(
+            resolve(1, None).unwrap(),
+            InputSource::File(workspace_input.clone())
+        );
+
+        // one directory per day
+        fs::create_dir_all(dir.join("day01")).unwrap();
+        fs::write(dir.join("day01/input"), "").unwrap();
+        assert_eq!(
+            resolve(1, None).unwrap(),
+            InputSource::File(dir.join("day01/input"))
+        );
+
+        // a flat directory of `dayNN` files
+        fs::write(dir.join("day02"), "").unwrap();
+        assert_eq!(
+            resolve(2, None).unwrap(),
+            InputSource::File(dir.join("day02"))
+        );
+
+        // `--input` still wins
+        let explicit = dir.join("explicit.txt");
+        fs::write(&explicit, "").unwrap();
+        assert_eq!(
+            resolve(2, Some(&explicit)).unwrap(),
+            InputSource::File(explicit)
+        );
+
+        // every path that was tried is listed
+        let err = resolve(25, None).unwrap_err();
+        let tried = [
+            dir.join("day25"),
+            dir.join("day25").join("input"),
+            day_dir(25).join("input"),
+        ];
+        match &err {
+            InputError::NotFound { day: 25, tried: t } => assert_eq!(t, &tried),
+            err => panic!("expected NotFound, got {:?}", err),
+        }
+        let listed = tried
+            .iter()
+            .map(|path| format!("\n  {}", path.display()))
+            .collect::<String>();
+        assert_eq!(
+            err.to_string(),
+            format!("could not find the input for day 25, tried:{}", listed)
+        );
+
+        env::remove_var(INPUT_DIR_VAR);
+        match resolve(25, None) {
+            Err(InputError::NotFound { tried, .. }) => {
+                assert_eq!(tried, [day_dir(25).join("input")])
+            }
+            res => panic!("expected NotFound, got {:?}", res),
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
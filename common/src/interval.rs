@@ -0,0 +1,606 @@
+//! Half-open integer intervals, sets of them, and piecewise-linear maps between them.
+use std::{fmt, ops};
+
+/// the integers `start..end`, `end` being excluded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Interval {
+    pub fn new(start: usize, end: usize) -> Self {
+        assert!(
+            start <= end,
+            "interval {}..{} ends before it starts",
+            start,
+            end
+        );
+        Interval { start, end }
+    }
+
+    pub fn with_len(start: usize, len: usize) -> Self {
+        Interval::new(start, start + len)
+    }
+
+    /// `len` values from `start`, unless they run past the largest `usize`
+    pub fn checked_with_len(start: usize, len: usize) -> Option<Self> {
+        Some(Interval::new(start, start.checked_add(len)?))
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        value >= self.start && value < self.end
+    }
+
+    /// the values in both intervals, unless they do not overlap
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        (start < end).then_some(Interval { start, end })
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.intersection(other).is_some()
+    }
+}
+
+impl From<ops::Range<usize>> for Interval {
+    fn from(range: ops::Range<usize>) -> Self {
+        Interval::new(range.start, range.end)
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// A set of integers stored as sorted intervals. The intervals never overlap or touch, so two
+/// sets with the same values are always equal.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    /// sorts the intervals and joins the ones that overlap or touch
+    fn normalized(mut intervals: Vec<Interval>) -> Self {
+        intervals.retain(|i| !i.is_empty());
+        intervals.sort();
+        let mut merged = Vec::<Interval>::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if last.end >= interval.start => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        IntervalSet { intervals: merged }
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        *self = self.union(&IntervalSet::from_iter([interval]));
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        let idx = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(idx).is_some_and(|i| i.contains(value))
+    }
+
+    /// the number of values in the set
+    pub fn len(&self) -> usize {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn min(&self) -> Option<usize> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn max(&self) -> Option<usize> {
+        self.intervals.last().map(|i| i.end - 1)
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        IntervalSet::normalized([&self.intervals[..], &other.intervals[..]].concat())
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut res = Vec::new();
+        let (mut a, mut b) = (0, 0);
+        while let (Some(x), Some(y)) = (self.intervals.get(a), other.intervals.get(b)) {
+            res.extend(x.intersection(y));
+            // whichever ends first cannot overlap anything else in the other set
+            match x.end <= y.end {
+                true => a += 1,
+                false => b += 1,
+            }
+        }
+        IntervalSet { intervals: res }
+    }
+
+    /// the values of this set that are not in `other`
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut res = Vec::new();
+        let mut b = 0;
+        for x in self.intervals.iter() {
+            let mut start = x.start;
+            // skip the intervals that end before this one starts
+            while other.intervals.get(b).is_some_and(|y| y.end <= start) {
+                b += 1;
+            }
+            let mut next = b;
+            while let Some(y) = other.intervals.get(next).filter(|y| y.start < x.end) {
+                if y.start > start {
+                    res.push(Interval::new(start, y.start));
+                }
+                start = start.max(y.end);
+                next += 1;
+            }
+            if start < x.end {
+                res.push(Interval::new(start, x.end));
+            }
+        }
+        IntervalSet { intervals: res }
+    }
+
+    /// the intervals of the set, cut in two at every point that falls strictly inside one
+    pub fn split_at(&self, points: &[usize]) -> Vec<Interval> {
+        let mut points = points.to_vec();
+        points.sort();
+        let mut res = Vec::new();
+        for interval in self.intervals.iter() {
+            let mut start = interval.start;
+            let first = points.partition_point(|&p| p <= start);
+            for &point in points[first..].iter().take_while(|&&p| p < interval.end) {
+                if point > start {
+                    res.push(Interval::new(start, point));
+                    start = point;
+                }
+            }
+            res.push(Interval::new(start, interval.end));
+        }
+        res
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        IntervalSet::normalized(iter.into_iter().collect())
+    }
+}
+
+/// the values in `source` are moved to the interval of the same length starting at `dest`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Piece {
+    pub source: Interval,
+    pub dest: usize,
+}
+
+impl Piece {
+    pub fn dest_interval(&self) -> Interval {
+        Interval::with_len(self.dest, self.source.len())
+    }
+
+    fn get(&self, value: usize) -> usize {
+        self.dest + (value - self.source.start)
+    }
+}
+
+/// two pieces, by their position in the order they were given, whose sources overlap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overlap {
+    pub first: usize,
+    pub second: usize,
+}
+
+/// A function on the integers that moves whole intervals at a time. Values outside every piece
+/// map to themselves.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PiecewiseMap {
+    /// sorted by source, without overlaps
+    pieces: Vec<Piece>,
+}
+
+impl PiecewiseMap {
+    /// the map where every value maps to itself
+    pub fn identity() -> Self {
+        PiecewiseMap::default()
+    }
+
+    /// builds a map from pieces whose sources do not overlap
+    pub fn new(pieces: impl IntoIterator<Item = Piece>) -> Result<Self, Overlap> {
+        let mut pieces = pieces
+            .into_iter()
+            .enumerate()
+            .filter(|(_, p)| !p.source.is_empty())
+            .collect::<Vec<_>>();
+        pieces.sort_by_key(|(_, p)| p.source);
+        if let Some(w) = pieces
+            .windows(2)
+            .find(|w| w[0].1.source.end > w[1].1.source.start)
+        {
+            return Err(Overlap {
+                first: w[0].0.min(w[1].0),
+                second: w[0].0.max(w[1].0),
+            });
+        }
+        Ok(PiecewiseMap {
+            pieces: pieces.into_iter().map(|(_, p)| p).collect(),
+        })
+    }
+
+    /// Builds a map from pieces that are known not to overlap, leaving out the ones that map to
+    /// themselves and joining the ones that continue each other.
+    fn simplified(mut pieces: Vec<Piece>) -> Self {
+        pieces.sort_by_key(|p| p.source);
+        let mut res = Vec::<Piece>::with_capacity(pieces.len());
+        for piece in pieces
+            .into_iter()
+            .filter(|p| !p.source.is_empty() && p.source.start != p.dest)
+        {
+            match res.last_mut() {
+                Some(last)
+                    if last.source.end == piece.source.start
+                        && last.dest_interval().end == piece.dest =>
+                {
+                    last.source.end = piece.source.end
+                }
+                _ => res.push(piece),
+            }
+        }
+        PiecewiseMap { pieces: res }
+    }
+
+    /// the pieces, sorted by source
+    pub fn pieces(&self) -> &[Piece] {
+        &self.pieces
+    }
+
+    /// the piece whose source contains `value`
+    pub fn piece(&self, value: usize) -> Option<&Piece> {
+        let idx = self.pieces.partition_point(|p| p.source.end <= value);
+        self.pieces.get(idx).filter(|p| p.source.contains(value))
+    }
+
+    pub fn get(&self, value: usize) -> usize {
+        match self.piece(value) {
+            Some(piece) => piece.get(value),
+            None => value,
+        }
+    }
+
+    /// Maps values given in ascending order. This walks the pieces once alongside the values,
+    /// instead of searching for the piece of every value.
+    pub fn get_sorted(&self, values: &[usize]) -> Vec<usize> {
+        debug_assert!(
+            values.windows(2).all(|w| w[0] <= w[1]),
+            "values are not sorted"
+        );
+        let mut pieces = self.pieces.iter().peekable();
+        values
+            .iter()
+            .map(|&value| {
+                while pieces.next_if(|p| p.source.end <= value).is_some() {}
+                match pieces.peek() {
+                    Some(piece) if piece.source.contains(value) => piece.get(value),
+                    _ => value,
+                }
+            })
+            .collect()
+    }
+
+    /// every value that maps to `value`
+    pub fn preimage(&self, value: usize) -> Vec<usize> {
+        let mut sources = self
+            .pieces
+            .iter()
+            .filter(|p| p.dest_interval().contains(value))
+            .map(|p| p.source.start + (value - p.dest))
+            .collect::<Vec<_>>();
+        if self.piece(value).is_none() {
+            sources.push(value);
+        }
+        sources
+    }
+
+    /// where the map stops being one linear piece
+    fn boundaries(&self) -> Vec<usize> {
+        self.pieces
+            .iter()
+            .flat_map(|p| [p.source.start, p.source.end])
+            .collect()
+    }
+
+    /// the image of a set, splitting its intervals wherever they cross the edge of a piece
+    pub fn map_set(&self, set: &IntervalSet) -> IntervalSet {
+        set.split_at(&self.boundaries())
+            .into_iter()
+            .map(|i| Interval::with_len(self.get(i.start), i.len()))
+            .collect()
+    }
+
+    /// an interval cut wherever it crosses the edge of a piece, each part with where it maps to
+    pub fn split(&self, interval: Interval) -> Vec<Piece> {
+        IntervalSet::from_iter([interval])
+            .split_at(&self.boundaries())
+            .into_iter()
+            .map(|source| Piece {
+                source,
+                dest: self.get(source.start),
+            })
+            .collect()
+    }
+
+    /// the map that applies `self` and then `other`
+    pub fn compose(&self, other: &PiecewiseMap) -> PiecewiseMap {
+        // both maps are linear between these points: the edges of this map's pieces and the edges
+        // of the other map's pieces pulled back through this map
+        let mut points = vec![0];
+        points.extend(self.boundaries());
+        for edge in other.boundaries() {
+            points.extend(self.preimage(edge));
+        }
+        points.sort();
+        points.dedup();
+
+        // past the last point neither map has any pieces left, so it is the identity
+        let pieces = points
+            .windows(2)
+            .map(|w| Piece {
+                source: Interval::new(w[0], w[1]),
+                dest: other.get(self.get(w[0])),
+            })
+            .collect();
+        PiecewiseMap::simplified(pieces)
+    }
+
+    /// The map from destinations back to sources. This only exists if the map is a bijection,
+    /// which is when its pieces cover the same values as sources as they do as destinations.
+    pub fn invert(&self) -> Option<PiecewiseMap> {
+        let sources = self
+            .pieces
+            .iter()
+            .map(|p| p.source)
+            .collect::<IntervalSet>();
+        let dests = self
+            .pieces
+            .iter()
+            .map(Piece::dest_interval)
+            .collect::<IntervalSet>();
+        // overlapping destinations show up as a set smaller than the pieces put together
+        let total = self.pieces.iter().map(|p| p.source.len()).sum::<usize>();
+        if sources != dests || dests.len() != total {
+            return None;
+        }
+
+        Some(PiecewiseMap::simplified(
+            self.pieces
+                .iter()
+                .map(|p| Piece {
+                    source: p.dest_interval(),
+                    dest: p.source.start,
+                })
+                .collect(),
+        ))
+    }
+}
+
+impl fmt::Display for PiecewiseMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for piece in self.pieces.iter() {
+            writeln!(f, "{} -> {}", piece.source, piece.dest_interval())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    /// every property is checked on values below this, against a bitset of them
+    const UNIVERSE: usize = 64;
+    const CASES: usize = 500;
+
+    /// random cases, the same on every run
+    trait Cases {
+        fn interval(&mut self) -> Interval;
+        fn set(&mut self) -> (IntervalSet, u64);
+        fn map(&mut self) -> PiecewiseMap;
+        fn permutation(&mut self) -> PiecewiseMap;
+    }
+
+    impl Cases for Rng {
+        fn interval(&mut self) -> Interval {
+            let (a, b) = (self.below(UNIVERSE + 1), self.below(UNIVERSE + 1));
+            Interval::new(a.min(b), a.max(b))
+        }
+
+        fn set(&mut self) -> (IntervalSet, u64) {
+            let intervals = (0..self.below(5))
+                .map(|_| self.interval())
+                .collect::<Vec<_>>();
+            let bits = intervals.iter().fold(0, |bits, i| bits | mask(i));
+            (intervals.into_iter().collect(), bits)
+        }
+
+        /// a map whose pieces stay inside the universe
+        fn map(&mut self) -> PiecewiseMap {
+            let mut points = (0..self.below(6) * 2)
+                .map(|_| self.below(UNIVERSE + 1))
+                .collect::<Vec<_>>();
+            points.sort();
+            let pieces = points
+                .chunks(2)
+                .map(|w| Interval::new(w[0], w[1]))
+                .map(|source| Piece {
+                    source,
+                    dest: self.below(UNIVERSE - source.len() + 1),
+                })
+                .collect::<Vec<_>>();
+            PiecewiseMap::new(pieces).unwrap()
+        }
+
+        /// a bijection: the universe cut into pieces and shuffled
+        fn permutation(&mut self) -> PiecewiseMap {
+            let mut points = (0..self.below(6))
+                .map(|_| self.below(UNIVERSE))
+                .chain([0, UNIVERSE])
+                .collect::<Vec<_>>();
+            points.sort();
+            points.dedup();
+            let mut sources = points
+                .windows(2)
+                .map(|w| Interval::new(w[0], w[1]))
+                .collect::<Vec<_>>();
+            let mut dests = sources.clone();
+            for i in (1..dests.len()).rev() {
+                dests.swap(i, self.below(i + 1));
+            }
+            // lay the shuffled pieces out one after the other
+            let mut dest = 0;
+            let mut pieces = Vec::new();
+            for shuffled in dests {
+                let source = sources.remove(sources.iter().position(|s| *s == shuffled).unwrap());
+                pieces.push(Piece { source, dest });
+                dest += source.len();
+            }
+            PiecewiseMap::new(pieces).unwrap()
+        }
+    }
+
+    fn mask(interval: &Interval) -> u64 {
+        (interval.start..interval.end).fold(0, |bits, v| bits | 1 << v)
+    }
+
+    fn bits(set: &IntervalSet) -> u64 {
+        assert!(set.max().is_none_or(|max| max < UNIVERSE));
+        set.intervals().iter().fold(0, |bits, i| bits | mask(i))
+    }
+
+    fn is_normalized(set: &IntervalSet) -> bool {
+        set.intervals().iter().all(|i| !i.is_empty())
+            && set.intervals().windows(2).all(|w| w[0].end < w[1].start)
+    }
+
+    #[test]
+    fn set_operations_match_a_bitset() {
+        let mut rng = Rng::new(0x5eed);
+        for _ in 0..CASES {
+            let ((a, a_bits), (b, b_bits)) = (rng.set(), rng.set());
+            for (set, expected) in [
+                (a.union(&b), a_bits | b_bits),
+                (a.intersection(&b), a_bits & b_bits),
+                (a.difference(&b), a_bits & !b_bits),
+            ] {
+                assert!(is_normalized(&set), "{:?}", set);
+                assert_eq!(bits(&set), expected, "{:?} {:?}", a, b);
+            }
+
+            assert_eq!(a.len(), a_bits.count_ones() as usize);
+            let value = rng.below(UNIVERSE);
+            assert_eq!(a.contains(value), a_bits & 1 << value != 0);
+        }
+    }
+
+    #[test]
+    fn split_keeps_the_values() {
+        let mut rng = Rng::new(0xc0ffee);
+        for _ in 0..CASES {
+            let (set, set_bits) = rng.set();
+            let points = (0..rng.below(6))
+                .map(|_| rng.below(UNIVERSE))
+                .collect::<Vec<_>>();
+            let pieces = set.split_at(&points);
+
+            assert_eq!(pieces.iter().fold(0, |bits, i| bits | mask(i)), set_bits);
+            assert!(pieces.windows(2).all(|w| w[0].end <= w[1].start));
+            for piece in pieces.iter() {
+                assert!(!piece.is_empty());
+                assert!(!points.iter().any(|&p| p > piece.start && p < piece.end));
+            }
+        }
+    }
+
+    #[test]
+    fn maps_match_brute_force() {
+        let mut rng = Rng::new(0xfeed);
+        for _ in 0..CASES {
+            let (f, g) = (rng.map(), rng.map());
+            let (set, set_bits) = rng.set();
+
+            let image = (0..UNIVERSE)
+                .filter(|v| set_bits & 1 << v != 0)
+                .fold(0, |bits, v| bits | 1 << f.get(v));
+            assert_eq!(bits(&f.map_set(&set)), image, "{}", f);
+
+            let values = (0..UNIVERSE).collect::<Vec<_>>();
+            let mapped = values.iter().map(|&v| f.get(v)).collect::<Vec<_>>();
+            assert_eq!(f.get_sorted(&values), mapped, "{}", f);
+
+            let composed = f.compose(&g);
+            for value in 0..UNIVERSE {
+                assert_eq!(composed.get(value), g.get(f.get(value)), "{}\n{}", f, g);
+                for source in f.preimage(value) {
+                    assert_eq!(f.get(source), value);
+                }
+            }
+
+            let injective = (0..UNIVERSE)
+                .map(|v| 1u64 << f.get(v))
+                .fold(0, |bits, b| bits | b)
+                == u64::MAX;
+            assert_eq!(f.invert().is_some(), injective, "{}", f);
+        }
+    }
+
+    #[test]
+    fn permutations_invert() {
+        let mut rng = Rng::new(0xbeef);
+        for _ in 0..CASES {
+            let f = rng.permutation();
+            let inverse = f.invert().unwrap();
+            for value in 0..UNIVERSE {
+                assert_eq!(inverse.get(f.get(value)), value, "{}", f);
+            }
+            assert_eq!(f.compose(&inverse), PiecewiseMap::identity());
+        }
+    }
+
+    #[test]
+    fn rejects_overlapping_pieces() {
+        let piece = |start, end, dest| Piece {
+            source: Interval::new(start, end),
+            dest,
+        };
+        let overlap = PiecewiseMap::new([piece(10, 20, 0), piece(0, 5, 50), piece(15, 30, 90)]);
+        assert_eq!(
+            overlap,
+            Err(Overlap {
+                first: 0,
+                second: 2
+            })
+        );
+    }
+}
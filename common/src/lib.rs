@@ -1,10 +1,26 @@
 use std::fs::File;
 use std::io::{self, BufRead};
 use std::path::Path;
+
+pub mod bench;
+mod error;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod parse;
+pub mod puzzle;
+pub mod rng;
+mod solution;
+
+pub use error::{AocError, Result};
+pub use solution::{run, Answer, Part, Report, Solution, Solver};
+
 // The output is wrapped in a Result to allow matching on errors
 // Returns an Iterator to the Reader of the lines of the file.
 pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
+where
+    P: AsRef<Path>,
+{
     let file = File::open(filename)?;
     Ok(io::BufReader::new(file).lines())
 }
@@ -0,0 +1,192 @@
+//! Helpers for the whitespace-separated number layouts most puzzle inputs use.
+//!
+//! Every value comes back with the span (byte range) it was read from, relative to the text
+//! passed to the top-level helper, so errors can point at the exact token. A [`ParseError`] is
+//! turned into an [`AocError`] with the line it came from using [`ParseError::on_line`].
+use std::{fmt::Display, ops::Range, str::FromStr};
+
+use crate::AocError;
+
+pub type Span = Range<usize>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub span: Span,
+    pub message: String,
+}
+
+impl ParseError {
+    /// the error as an [`AocError`] on the given line, `line_idx` being the 0-based index of it
+    pub fn on_line(self, line_idx: usize, line: &str) -> AocError {
+        let start = self.span.start.min(line.len());
+        AocError {
+            column: Some(line[..start].chars().count() + 1),
+            ..AocError::at_line(line_idx, self.message)
+        }
+    }
+}
+
+/// a `key: values | values | ...` line, such as `Card 1: 41 48 | 83 86`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyValues<'a, T> {
+    pub key: Spanned<&'a str>,
+    pub groups: Vec<Vec<Spanned<T>>>,
+}
+
+/// drops the spans, once they are no longer needed for errors
+pub fn values<T>(items: Vec<Spanned<T>>) -> Vec<T> {
+    items.into_iter().map(|item| item.value).collect()
+}
+
+impl<'a> Spanned<&'a str> {
+    /// the whitespace-separated tokens of this text
+    pub fn tokens(&self) -> Vec<Spanned<&'a str>> {
+        let text = self.value;
+        text.split_whitespace()
+            .map(|token| {
+                let start = self.span.start + (token.as_ptr() as usize - text.as_ptr() as usize);
+                Spanned {
+                    value: token,
+                    span: start..start + token.len(),
+                }
+            })
+            .collect()
+    }
+
+    /// parses every whitespace-separated token as an integer, whether `T` is signed or unsigned
+    pub fn integers<T>(&self) -> Result<Vec<Spanned<T>>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.tokens()
+            .into_iter()
+            .map(|token| match token.value.parse::<T>() {
+                Ok(value) => Ok(Spanned {
+                    value,
+                    span: token.span,
+                }),
+                Err(e) => Err(ParseError {
+                    message: format!("invalid number {:?}: {}", token.value, e),
+                    span: token.span,
+                }),
+            })
+            .collect()
+    }
+
+    /// the part of this text between `start` and `end` (byte offsets into it)
+    fn slice(&self, start: usize, end: usize) -> Spanned<&'a str> {
+        Spanned {
+            value: &self.value[start..end],
+            span: self.span.start + start..self.span.start + end,
+        }
+    }
+
+    /// splits at the first `separator`, leaving it out of both halves
+    fn split_once(&self, separator: char) -> Option<(Spanned<&'a str>, Spanned<&'a str>)> {
+        let at = self.value.find(separator)?;
+        Some((
+            self.slice(0, at),
+            self.slice(at + separator.len_utf8(), self.value.len()),
+        ))
+    }
+}
+
+fn whole(text: &str) -> Spanned<&str> {
+    Spanned {
+        value: text,
+        span: 0..text.len(),
+    }
+}
+
+/// the whitespace-separated tokens of `text`
+pub fn tokens(text: &str) -> Vec<Spanned<&str>> {
+    whole(text).tokens()
+}
+
+/// every whitespace-separated token of `text` parsed as an integer
+pub fn integers<T>(text: &str) -> Result<Vec<Spanned<T>>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    whole(text).integers()
+}
+
+/// the text after a fixed label, such as `Time:` or `seeds:`
+pub fn after_label<'a>(text: &'a str, label: &str) -> Result<Spanned<&'a str>, ParseError> {
+    match text.starts_with(label) {
+        true => Ok(whole(text).slice(label.len(), text.len())),
+        false => Err(ParseError {
+            span: 0..text.len().min(label.len()),
+            message: format!("expected a line starting with {:?}", label),
+        }),
+    }
+}
+
+/// splits `label: rest` at the first colon, for labels that vary such as `Card 12`
+pub fn split_label(text: &str) -> Result<(Spanned<&str>, Spanned<&str>), ParseError> {
+    whole(text).split_once(':').ok_or(ParseError {
+        span: 0..text.len(),
+        message: "expected `<label>: <values>`".to_string(),
+    })
+}
+
+/// parses a `key: values | values | ...` line, splitting the values into groups at each `|`
+pub fn key_values<T>(text: &str) -> Result<KeyValues<'_, T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let (key, mut rest) = split_label(text)?;
+    let mut groups = Vec::new();
+    while let Some((group, remaining)) = rest.split_once('|') {
+        groups.push(group.integers()?);
+        rest = remaining;
+    }
+    groups.push(rest.integers()?);
+
+    Ok(KeyValues {
+        key: Spanned {
+            value: key.value.trim(),
+            span: key.span,
+        },
+        groups,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_keep_their_spans() {
+        let numbers = integers::<i64>("10  -3 7").unwrap();
+        assert_eq!(values(numbers.clone()), vec![10, -3, 7]);
+        assert_eq!(numbers[1].span, 4..6);
+
+        let err = integers::<u32>("1 -2").unwrap_err();
+        assert_eq!(err.span, 2..4);
+        assert_eq!(err.on_line(3, "1 -2").column, Some(3));
+    }
+
+    #[test]
+    fn key_values_are_split_into_groups() {
+        let line = "Card   1: 41 48 | 83  86 6";
+        let card = key_values::<u32>(line).unwrap();
+        assert_eq!(card.key.value, "Card   1");
+        assert_eq!(card.groups.len(), 2);
+        assert_eq!(values(card.groups[1].clone()), vec![83, 86, 6]);
+        assert_eq!(&line[card.groups[1][1].span.clone()], "86");
+
+        let label = after_label("Time:      7  15", "Time:").unwrap();
+        assert_eq!(values(label.integers::<u8>().unwrap()), vec![7, 15]);
+        assert!(after_label("Distance: 9", "Time:").is_err());
+    }
+}
@@ -0,0 +1,224 @@
+//! Pulling the worked examples out of a day's `puzzle.md`, so they can be run as tests.
+//!
+//! The puzzle text is split into one section per part (at `--- Part Two ---`). In each section,
+//! fenced code blocks hold the example inputs and emphasized code spans (`` `*142*` ``) hold the
+//! values computed from them; the last one in a section is that part's example answer.
+use crate::{Part, Solution};
+
+const PART_TWO_HEADER: &str = "--- Part Two ---";
+const FENCE: &str = "```";
+
+/// a fenced code block, along with the line of text introducing it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub intro: String,
+    pub content: String,
+}
+
+impl Block {
+    /// whether the text presents this block as a new example, rather than one repeated from earlier
+    fn is_new_example(&self) -> bool {
+        self.intro.to_lowercase().ends_with("for example:")
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Section {
+    pub blocks: Vec<Block>,
+    pub answers: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answer: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    pub sections: Vec<Section>,
+}
+
+fn parse_answers(line: &str) -> Vec<String> {
+    // every other piece between backticks is a code span
+    line.split('`')
+        .skip(1)
+        .step_by(2)
+        .filter_map(|span| span.strip_prefix('*')?.strip_suffix('*'))
+        .filter(|answer| !answer.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn parse_section(text: &str) -> Section {
+    let mut section = Section::default();
+    let mut intro = "";
+    let mut block: Option<Vec<&str>> = None;
+
+    for line in text.lines() {
+        match (&mut block, line.trim_end() == FENCE) {
+            (None, true) => block = Some(Vec::new()),
+            (Some(lines), true) => {
+                // examples are stored the way input files are, with a single trailing newline
+                let content = lines.join("\n").trim_end().to_string() + "\n";
+                section.blocks.push(Block {
+                    intro: intro.to_string(),
+                    content,
+                });
+                block = None;
+            }
+            (Some(lines), false) => lines.push(line),
+            (None, false) => {
+                if !line.trim().is_empty() {
+                    intro = line.trim();
+                }
+                section.answers.extend(parse_answers(line));
+            }
+        }
+    }
+    section
+}
+
+impl Puzzle {
+    pub fn parse(markdown: &str) -> Self {
+        Puzzle {
+            sections: markdown.split(PART_TWO_HEADER).map(parse_section).collect(),
+        }
+    }
+
+    pub fn section(&self, part: Part) -> Option<&Section> {
+        match part {
+            Part::One => self.sections.first(),
+            Part::Two => self.sections.get(1),
+        }
+    }
+
+    /// the example for a part, using the given code block of that part's section as its input
+    pub fn example_at(&self, part: Part, block: usize) -> Option<Example> {
+        let section = self.section(part)?;
+        Some(Example {
+            input: section.blocks.get(block)?.content.clone(),
+            answer: section.answers.last()?.clone(),
+        })
+    }
+
+    /// The example for a part. This is the first code block of the part's section, except that
+    /// part 2 reuses the part 1 example unless its section introduces a new one.
+    pub fn example(&self, part: Part) -> Option<Example> {
+        let answer = self.section(part)?.answers.last()?.clone();
+        let part1 = self.section(Part::One)?.blocks.first();
+        let block = match part {
+            Part::One => part1,
+            Part::Two => self
+                .section(Part::Two)?
+                .blocks
+                .first()
+                .filter(|b| b.is_new_example())
+                .or(part1),
+        }?;
+
+        Some(Example {
+            input: block.content.clone(),
+            answer,
+        })
+    }
+}
+
+/// Runs a solution on the example from `puzzle.md` and checks it against the expected answer.
+/// `block` picks a specific code block of the part's section instead of the default example.
+pub fn assert_example<S: Solution>(markdown: &str, part: Part, block: Option<usize>) {
+    let puzzle = Puzzle::parse(markdown);
+    let example = match block {
+        Some(block) => puzzle.example_at(part, block),
+        None => puzzle.example(part),
+    }
+    .unwrap_or_else(|| panic!("puzzle.md has no example for part {}", part));
+
+    let input = S::parse(&example.input)
+        .and_then(|input| S::check(&input, part).map(|_| input))
+        .unwrap_or_else(|e| panic!("{}", e));
+    let answer = match part {
+        Part::One => S::part1(&input).to_string(),
+        Part::Two => S::part2(&input).to_string(),
+    };
+    assert_eq!(
+        answer, example.answer,
+        "part {} on the example:\n{}",
+        part, example.input
+    );
+}
+
+/// Generates a test per listed part that runs the day's solution on the examples in its
+/// `puzzle.md`:
+///
+/// ```ignore
+/// common::example_tests!(Day08: part1 = 1);
+/// common::example_tests!(Day07: part1, part2);
+/// ```
+///
+/// `= n` picks the n-th code block of the part's section as the example input, and any
+/// attributes (such as `#[ignore]`) are copied onto the generated test.
+#[macro_export]
+macro_rules! example_tests {
+    (@part part1) => { $crate::Part::One };
+    (@part part2) => { $crate::Part::Two };
+    ($solution:ty: $($(#[$attr:meta])* $part:ident $(= $block:expr)?),+ $(,)?) => {
+        $(
+            #[test]
+            $(#[$attr])*
+            fn $part() {
+                $crate::puzzle::assert_example::<$solution>(
+                    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/puzzle.md")),
+                    $crate::example_tests!(@part $part),
+                    None $(.or(Some($block)))?,
+                );
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MARKDOWN: &str = "\
+For example:
+
+```
+1abc2
+treb7uchet
+
+```
+
+Adding these together produces `*142*`.
+
+\\--- Part Two ---
+----------
+
+Consider the same document again:
+
+```
+1abc2
+```
+
+The `*` is not an answer, this time it produces `*281*`.
+";
+
+    #[test]
+    fn reuses_the_part1_example_unless_a_new_one_is_given() {
+        let puzzle = Puzzle::parse(MARKDOWN);
+        let expected = Example {
+            input: "1abc2\ntreb7uchet\n".to_string(),
+            answer: "142".to_string(),
+        };
+        assert_eq!(puzzle.example(Part::One), Some(expected.clone()));
+        assert_eq!(
+            puzzle.example(Part::Two),
+            Some(Example {
+                answer: "281".to_string(),
+                ..expected
+            })
+        );
+        assert_eq!(puzzle.example_at(Part::Two, 0).unwrap().input, "1abc2\n");
+    }
+}
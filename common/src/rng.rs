@@ -0,0 +1,28 @@
+//! A small seeded random number generator, for anything that has to come out the same on every
+//! run: generated test cases, or hands dealt for a simulation.
+
+/// a xorshift generator, so that a seed always gives the same values
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift never leaves zero
+        Rng(match seed {
+            0 => 0x9e37_79b9_7f4a_7c15,
+            seed => seed,
+        })
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// a value in `0..n`
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
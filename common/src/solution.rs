@@ -0,0 +1,156 @@
+use std::{
+    fmt::{self, Display},
+    process,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    bench::{self, Step, Timing},
+    input, AocError,
+};
+
+/// A single day's puzzle: the input is parsed once and both parts are answered from it.
+pub trait Solution {
+    /// the day of the month this solution belongs to
+    const DAY: u8;
+
+    /// the parsed puzzle input, shared by both parts
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+
+    /// Checks that the parsed input suits one part, for puzzles whose parts read the input
+    /// differently: `parse` only rejects what neither part can use, and a part only runs once its
+    /// check passes.
+    fn check(_input: &Self::Input, _part: Part) -> Result<(), AocError> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Answer {
+    pub part: Part,
+    pub value: String,
+    pub elapsed: Duration,
+}
+
+/// the answers for one run of a day, along with how long each step took
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub day: u8,
+    pub parse_time: Duration,
+    pub answers: Vec<Answer>,
+}
+
+/// Object-safe view of a [`Solution`], so days with different input and answer types can be
+/// kept in one list and driven by the same harness.
+pub trait Solver {
+    fn day(&self) -> u8;
+    fn solve(&self, input: &str, parts: &[Part]) -> Result<Report, AocError>;
+    /// times parsing and each part separately, running each one `iterations` times
+    fn bench(&self, input: &str, iterations: usize) -> Result<Vec<Timing>, AocError>;
+}
+
+impl<S: Solution> Solver for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn solve(&self, input: &str, parts: &[Part]) -> Result<Report, AocError> {
+        let start = Instant::now();
+        let parsed = S::parse(input)?;
+        let parse_time = start.elapsed();
+
+        let answers = parts
+            .iter()
+            .map(|&part| {
+                S::check(&parsed, part)?;
+                let start = Instant::now();
+                let value = match part {
+                    Part::One => S::part1(&parsed).to_string(),
+                    Part::Two => S::part2(&parsed).to_string(),
+                };
+                Ok(Answer {
+                    part,
+                    value,
+                    elapsed: start.elapsed(),
+                })
+            })
+            .collect::<Result<_, AocError>>()?;
+
+        Ok(Report {
+            day: S::DAY,
+            parse_time,
+            answers,
+        })
+    }
+
+    fn bench(&self, input: &str, iterations: usize) -> Result<Vec<Timing>, AocError> {
+        // parse once up front, so a bad input is reported instead of timed
+        let parsed = S::parse(input)?;
+        for part in Part::BOTH {
+            S::check(&parsed, part)?;
+        }
+        Ok(Step::ALL
+            .iter()
+            .map(|&step| {
+                let stats = match step {
+                    Step::Parse => bench::measure(iterations, || S::parse(input)),
+                    Step::Part1 => bench::measure(iterations, || S::part1(&parsed)),
+                    Step::Part2 => bench::measure(iterations, || S::part2(&parsed)),
+                };
+                Timing { step, stats }
+            })
+            .collect())
+    }
+}
+
+/// The `main` of a day's binary: reads the input (see [`input::source_from_args`]) and prints both
+/// answers, or a diagnostic if the input could not be read or parsed or does not suit a part.
+pub fn run<S: Solution>() {
+    let res = input::source_from_args(S::DAY)
+        .map_err(AocError::from)
+        .and_then(|source| {
+            let content = input::read(&source)?;
+            let parsed = S::parse(&content).map_err(|e| e.in_file(&source))?;
+            Ok((source, parsed))
+        });
+    fn exit(e: AocError) -> ! {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+
+    let (source, parsed) = res.unwrap_or_else(|e| exit(e));
+    for part in Part::BOTH {
+        if let Err(e) = S::check(&parsed, part) {
+            exit(e.in_file(&source));
+        }
+        match part {
+            Part::One => println!("{}", S::part1(&parsed)),
+            Part::Two => println!("{}", S::part2(&parsed)),
+        }
+    }
+}
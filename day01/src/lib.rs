@@ -0,0 +1,76 @@
+use common::{AocError, Solution};
+
+pub struct Day01;
+
+fn is_digit_or_word(substr: &str) -> Option<u32> {
+    // given a substring, returns whether it starts with a digit, or a word that speels out a digit
+    let digits = ["0", "zero", "1", "one", "2", "two", "3", "three", "4", "four",  "5", "five", "6", "six", "7", "seven","8", "eight", "9", "nine"];
+    for (idx, d) in digits.iter().enumerate(){
+        if substr.starts_with(d) {
+            return Some((idx /2) as u32)
+        }
+    }
+    None
+}
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                match line
+                    .char_indices()
+                    .any(|(i, _)| is_digit_or_word(&line[i..]).is_some())
+                {
+                    true => Ok(line.to_string()),
+                    false => Err(AocError::at_line(idx, "expected a digit or a digit word")),
+                }
+            })
+            .collect()
+    }
+
+    fn part1(lines: &Self::Input) -> u32 {
+        let mut sum = 0;
+        // for each line in lines, split digits
+        for line in lines {
+            let digits : Vec<_> = line.chars().filter_map(|c| c.to_digit(10)).collect();
+            // a line may only spell its digits out, which part 1 does not read
+            if let (Some(first), Some(last)) = (digits.first(), digits.last()) {
+                sum += first * 10 + last;
+            }
+        }
+        sum
+    }
+
+    fn part2(lines: &Self::Input) -> u32 {
+        let mut sum = 0;
+        for l in lines {
+            let digits: Vec<_> = l.char_indices().map(|(idx,_)| &l[idx..] ).filter_map(is_digit_or_word).collect();
+            if let (Some(first), Some(last)) = (digits.first(), digits.last()) {
+                sum += first * 10 + last;
+            }
+        }
+        sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_lines_without_digits() {
+        let err = Day01::parse("1abc2\nabcdef\n").unwrap_err();
+        assert_eq!(err.line, Some(2));
+        let lines = Day01::parse("two1nine\neightwothree").unwrap();
+        assert_eq!(Day01::part1(&lines), 11);
+        assert_eq!(Day01::part2(&lines), 29 + 83);
+    }
+}
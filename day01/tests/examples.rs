@@ -0,0 +1,3 @@
+use day01::Day01;
+
+common::example_tests!(Day01: part1, part2);
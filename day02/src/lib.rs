@@ -0,0 +1,139 @@
+use common::{AocError, Solution};
+use regex::Regex;
+use std::cmp;
+
+pub struct Day02;
+
+#[derive(Debug, Clone)]
+pub struct GameResults {
+    red: u32,
+    green: u32,
+    blue: u32,
+}
+
+fn parse_subset(idx: usize, line: &str, subset: &str) -> Result<GameResults, AocError> {
+    let mut res = GameResults {
+        red: 0,
+        blue: 0,
+        green: 0,
+    };
+    let cubes_picked = subset.split(",");
+    for p in cubes_picked {
+        let p = p.trim();
+        let (count, color) = p.split_once(" ").ok_or_else(|| {
+            AocError::at(
+                idx,
+                line,
+                p,
+                format!("expected `<count> <color>`, found {:?}", p),
+            )
+        })?;
+        let count = count.parse::<u32>().map_err(|e| {
+            AocError::at(
+                idx,
+                line,
+                count,
+                format!("invalid cube count {:?}: {}", count, e),
+            )
+        })?;
+        match color {
+            "blue" => res.blue = count,
+            "green" => res.green = count,
+            "red" => res.red = count,
+            _ => {
+                return Err(AocError::at(
+                    idx,
+                    line,
+                    color,
+                    format!("unknown color {:?}", color),
+                ))
+            }
+        }
+    }
+    Ok(res)
+}
+
+fn parse_game_line(idx: usize, line: &str) -> Result<(u32, Vec<GameResults>), AocError> {
+    let re = Regex::new(r"^Game (?<id>[0-9]+): (?<rest>([^;]*;)*)(?<last>[^;]*)$").unwrap();
+    let cap = re
+        .captures(line)
+        .ok_or_else(|| AocError::at_line(idx, "expected `Game <id>: <cubes>; <cubes>...`"))?;
+    let id = cap["id"]
+        .parse::<u32>()
+        .map_err(|e| AocError::at(idx, line, &cap["id"], format!("invalid game id: {}", e)))?;
+    let mut all_picks = vec![&cap["last"]];
+    all_picks.extend(cap["rest"].split(";"));
+    let res = all_picks
+        .iter()
+        .filter(|&x| !x.trim().is_empty())
+        .map(|subset| parse_subset(idx, line, subset))
+        .collect::<Result<Vec<_>, _>>()?;
+    if res.is_empty() {
+        return Err(AocError::at(
+            idx,
+            line,
+            &line[line.len()..],
+            "expected at least one set of cubes",
+        ));
+    }
+    Ok((id, res))
+}
+
+fn get_max_cubes(game: &[GameResults]) -> GameResults {
+    let mut res = game[0].clone();
+    for pick in game {
+        res.red = cmp::max(res.red, pick.red);
+        res.green = cmp::max(res.green, pick.green);
+        res.blue = cmp::max(res.blue, pick.blue);
+    }
+
+    res
+}
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<(u32, Vec<GameResults>)>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| parse_game_line(idx, line))
+            .collect()
+    }
+
+    fn part1(games: &Self::Input) -> u32 {
+        let mut sum: u32 = 0;
+        for (id, game) in games {
+            let max_cubes = get_max_cubes(game);
+            if max_cubes.red <= 12 && max_cubes.green <= 13 && max_cubes.blue <= 14 {
+                sum += id;
+            }
+        }
+        sum
+    }
+
+    fn part2(games: &Self::Input) -> u32 {
+        let mut sum: u32 = 0;
+        for (_, game) in games {
+            let max_cubes = get_max_cubes(game);
+            sum += max_cubes.red * max_cubes.blue * max_cubes.green;
+        }
+        sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_games_without_picks() {
+        let err = Day02::parse("Game 1: 3 blue, 4 red\nGame 2: ").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(9)));
+        assert_eq!(err.to_string(), "2:9: expected at least one set of cubes");
+    }
+}
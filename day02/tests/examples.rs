@@ -0,0 +1,3 @@
+use day02::Day02;
+
+common::example_tests!(Day02: part1, part2);
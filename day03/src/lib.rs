@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use common::{
+    grid::{Coord, Grid},
+    AocError, Solution,
+};
+
+pub struct Day03;
+
+#[derive(Debug, Clone)]
+struct SchemaItem {
+    start: Coord,
+    end: Coord,
+    value: u32,
+}
+
+fn is_symbol(c: char) -> bool {
+    !(c.is_ascii_digit() || c == '.')
+}
+
+impl SchemaItem {
+    fn get_neighbor_coords(&self, scheme: &Grid<char>) -> Vec<Coord> {
+        // the neighbours of every digit, without the digits of the item itself
+        let mut neighbors = (self.start.x..=self.end.x)
+            .flat_map(|x| scheme.neighbors8(Coord::new(x, self.start.y)))
+            .filter(|c| c.y != self.start.y || c.x < self.start.x || c.x > self.end.x)
+            .collect::<Vec<_>>();
+        neighbors.sort();
+        neighbors.dedup();
+
+        neighbors
+    }
+}
+
+#[derive(Debug)]
+pub struct Schematic {
+    scheme: Grid<char>,
+    parts: Vec<SchemaItem>,
+}
+
+impl Schematic {
+    pub fn new(scheme: Grid<char>) -> Result<Self, AocError> {
+        let mut parts = Vec::new();
+
+        for (row, cells) in scheme.rows().enumerate() {
+            let mut found_item = "".to_string();
+            // iterate over all columns, finding digits
+            for (col, cur_char) in cells.iter().enumerate() {
+                if cur_char.is_ascii_digit() {
+                    found_item.push(*cur_char);
+                }
+
+                // if we reached the end of a part
+                if !found_item.is_empty() && (col == cells.len() - 1 || !cur_char.is_ascii_digit())
+                {
+                    // we just reached the end of an item
+
+                    let end_x = match cur_char.is_ascii_digit() {
+                        true => col,
+                        false => col - 1,
+                    };
+                    let start_x = end_x + 1 - found_item.len();
+                    let value = found_item.parse().map_err(|e| AocError {
+                        column: Some(start_x + 1),
+                        ..AocError::at_line(
+                            row,
+                            format!("invalid part number {}: {}", found_item, e),
+                        )
+                    })?;
+                    let item = SchemaItem {
+                        start: Coord::new(start_x, row),
+                        end: Coord::new(end_x, row),
+                        value,
+                    };
+
+                    // check if the item is a part
+                    let has_symbol_neighbor = item
+                        .get_neighbor_coords(&scheme)
+                        .iter()
+                        .any(|c| is_symbol(scheme[*c]));
+
+                    if has_symbol_neighbor {
+                        parts.push(item);
+                    }
+                    found_item = "".to_string();
+                }
+            }
+        }
+
+        Ok(Schematic { scheme, parts })
+    }
+}
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input = Schematic;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Schematic::new(Grid::parse(input)?)
+    }
+
+    fn part1(s: &Self::Input) -> u32 {
+        s.parts.iter().map(|p| p.value).sum()
+    }
+
+    fn part2(s: &Self::Input) -> u32 {
+        let mut possible_gears = HashMap::<Coord, Vec<SchemaItem>>::new();
+
+        for p in s.parts.iter() {
+            // for each part, find any '*' coordinates that it is adjacent to, and add them to the hashmap
+            p.get_neighbor_coords(&s.scheme)
+                .iter()
+                .filter(|c| s.scheme[**c] == '*')
+                .for_each(|c| possible_gears.entry(*c).or_default().push(p.clone()));
+        }
+
+        possible_gears
+            .iter()
+            .filter(|(_, v)| v.len() == 2)
+            .map(|(_, v)| v[0].value * v[1].value)
+            .sum()
+    }
+}
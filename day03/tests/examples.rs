@@ -0,0 +1,3 @@
+use day03::Day03;
+
+common::example_tests!(Day03: part1, part2);
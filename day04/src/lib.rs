@@ -0,0 +1,79 @@
+use std::collections::{HashMap, HashSet};
+
+use common::{parse, AocError, Solution};
+
+pub struct Day04;
+
+pub struct Card {
+    id: u32,
+    won_cards: Vec<u32>,
+}
+
+fn parse_card(idx: usize, card_str: &str) -> Result<Card, AocError> {
+    let card = parse::key_values::<u32>(card_str).map_err(|e| e.on_line(idx, card_str))?;
+    let id = parse::after_label(card.key.value, "Card")
+        .and_then(|id| id.integers::<u32>())
+        .map(parse::values)
+        .map_err(|e| e.on_line(idx, card_str))?;
+    let (&[id], [winning, have]) = (&id[..], &card.groups[..]) else {
+        return Err(AocError::at_line(
+            idx,
+            "expected `Card <id>: <winning numbers> | <numbers you have>`",
+        ));
+    };
+    let winning = winning.iter().map(|n| n.value).collect::<HashSet<_>>();
+    let have = have.iter().map(|n| n.value).collect::<HashSet<_>>();
+
+    let num_matches = winning.intersection(&have).count() as u32;
+    Ok(Card {
+        id,
+        won_cards: (id + 1..=id + num_matches).collect(),
+    })
+}
+
+fn score_card(card: &Card) -> usize {
+    match card.won_cards.len() {
+        0 => 0,
+        num_matches => 2usize.pow((num_matches - 1) as u32),
+    }
+}
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<Card>;
+    type Answer1 = usize;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| parse_card(idx, line))
+            .collect()
+    }
+
+    fn part1(cards: &Self::Input) -> usize {
+        cards.iter().map(score_card).sum()
+    }
+
+    fn part2(cards: &Self::Input) -> u32 {
+        let mut card_counts = HashMap::<u32, u32>::new();
+
+        for card in cards.iter() {
+            // always add the card we just found
+            let curr_count = card_counts.entry(card.id).or_insert(0);
+            *curr_count += 1u32;
+
+            // create a copy so we don't have 2 mutable references
+            let new_count = *curr_count;
+
+            // since we have multiple copies of this card, we add multiple instances of each card
+            for won_card in &card.won_cards {
+                *card_counts.entry(*won_card).or_insert(0) += new_count;
+            }
+        }
+
+        card_counts.values().sum::<u32>()
+    }
+}
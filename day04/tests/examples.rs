@@ -0,0 +1,3 @@
+use day04::Day04;
+
+common::example_tests!(Day04: part1);
@@ -0,0 +1,828 @@
+use std::{
+    collections::{HashMap, HashSet},
+    iter::{Enumerate, Peekable},
+    str::Lines,
+};
+
+use common::{
+    interval::{Interval, IntervalSet, Piece, PiecewiseMap},
+    parse::{self, ParseError, Spanned},
+    AocError, Part, Solution,
+};
+
+pub struct Day05;
+
+/// a `<dest start> <source start> <length>` line, before it is checked
+#[derive(Debug, Clone, Copy)]
+struct RawRange {
+    /// the index of the line it was read from
+    line: usize,
+    dest: usize,
+    source: usize,
+    len: usize,
+}
+
+impl RawRange {
+    fn parse(idx: usize, line: &str) -> Result<Self, AocError> {
+        let numbers = parse::integers::<usize>(line)
+            .map(parse::values)
+            .map_err(|e| e.on_line(idx, line))?;
+        let [dest, source, len] = numbers[..] else {
+            return Err(AocError::at_line(
+                idx,
+                format!(
+                    "expected `<dest start> <source start> <length>`, found {} numbers",
+                    numbers.len()
+                ),
+            ));
+        };
+
+        Ok(RawRange {
+            line: idx,
+            dest,
+            source,
+            len,
+        })
+    }
+
+    /// the source values, unless the range runs past the largest number
+    fn sources(&self) -> Option<Interval> {
+        Interval::checked_with_len(self.source, self.len)
+    }
+
+    fn dests(&self) -> Option<Interval> {
+        Interval::checked_with_len(self.dest, self.len)
+    }
+}
+
+/// the lines of the almanac along with their index, so errors can point at them
+type AlmanacLines<'a> = Peekable<Enumerate<Lines<'a>>>;
+
+const SEEDS: &str = "seed";
+const LOCATIONS: &str = "location";
+
+/// one `X-to-Y map:` section of the almanac, as it was read
+#[derive(Debug, Clone)]
+struct MapSection {
+    source: String,
+    dest: String,
+    /// the index of the title line
+    line: usize,
+    ranges: Vec<RawRange>,
+}
+
+/// one `X-to-Y map:` section of the almanac, once its ranges have been checked
+#[derive(Debug, Clone)]
+struct CategoryMap {
+    source: String,
+    dest: String,
+    ranges: PiecewiseMap,
+}
+
+/// the values of one category, on the way through a chain of maps
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stage {
+    pub category: String,
+    /// The values in this category, cut wherever a map on the way cut them and never merged back
+    /// together. Each piece's source is the piece of the previous category it was mapped from, the
+    /// first stage's pieces come from themselves.
+    pub pieces: Vec<Piece>,
+}
+
+impl Stage {
+    /// the values in this category, merged back together
+    pub fn values(&self) -> IntervalSet {
+        self.pieces.iter().map(Piece::dest_interval).collect()
+    }
+}
+
+/// The seeds and the maps between categories. The maps form a graph without cycles: a category
+/// can have maps to several others, as long as there is exactly one way from `seed` to
+/// `location`. Mapping between any other two categories needs the same.
+#[derive(Debug, Clone)]
+pub struct Almanac {
+    seeds: Vec<Spanned<usize>>,
+    /// the `seeds:` line, for errors about the seeds
+    seed_line: String,
+    maps: Vec<CategoryMap>,
+    /// the indices of the maps from `seed` to `location`, in the order they are applied
+    seed_to_location: Vec<usize>,
+}
+
+impl MapSection {
+    fn parse(peekable_lines: &mut AlmanacLines) -> Result<Self, AocError> {
+        // read empty line
+        match peekable_lines.next() {
+            Some((_, "")) => {}
+            Some((idx, _)) => return Err(AocError::at_line(idx, "expected an empty line")),
+            None => {
+                return Err(AocError::new(
+                    "unexpected end of input, expected another map",
+                ))
+            }
+        }
+        // read the title line
+        let (line, title) = peekable_lines
+            .next()
+            .ok_or_else(|| AocError::new("unexpected end of input, expected a map title"))?;
+        let (source, dest) = title
+            .strip_suffix(" map:")
+            .and_then(|name| name.split_once("-to-"))
+            .filter(|(source, dest)| !source.is_empty() && !dest.is_empty())
+            .ok_or_else(|| AocError::at_line(line, "expected a `X-to-Y map:` title"))?;
+
+        let mut ranges = Vec::new();
+        while let Some((idx, line)) = peekable_lines.next_if(|(_, line)| !line.is_empty()) {
+            ranges.push(RawRange::parse(idx, line)?);
+        }
+
+        Ok(MapSection {
+            source: source.to_string(),
+            dest: dest.to_string(),
+            line,
+            ranges,
+        })
+    }
+
+    /// Everything that keeps the ranges from being one well-defined map: ranges that run past the
+    /// largest number, sources that overlap (so a value has two destinations), and destinations
+    /// that overlap each other or the values no range covers, which map to themselves (so the map
+    /// is not one-to-one).
+    fn problems(&self) -> Vec<AocError> {
+        let name = format!("{}-to-{} map", self.source, self.dest);
+        let mut problems = Vec::new();
+        let mut checked = Vec::new();
+        for r in self.ranges.iter() {
+            match (r.sources(), r.dests()) {
+                (Some(sources), Some(dests)) => checked.push((r.line, sources, dests)),
+                (None, _) => problems.push(AocError::at_line(
+                    r.line,
+                    format!(
+                        "{}: the source range {} + {} overflows",
+                        name, r.source, r.len
+                    ),
+                )),
+                (_, None) => problems.push(AocError::at_line(
+                    r.line,
+                    format!(
+                        "{}: the destination range {} + {} overflows",
+                        name, r.dest, r.len
+                    ),
+                )),
+            }
+        }
+
+        for (i, (line, sources, dests)) in checked.iter().enumerate() {
+            for (earlier, earlier_sources, earlier_dests) in checked[..i].iter() {
+                if sources.overlaps(earlier_sources) {
+                    problems.push(AocError::at_line(
+                        *line,
+                        format!(
+                            "{}: the source range {} overlaps {} on line {}",
+                            name,
+                            sources,
+                            earlier_sources,
+                            earlier + 1
+                        ),
+                    ));
+                }
+                if dests.overlaps(earlier_dests) {
+                    problems.push(AocError::at_line(
+                        *line,
+                        format!(
+                            "{}: the destination range {} overlaps {} on line {}",
+                            name,
+                            dests,
+                            earlier_dests,
+                            earlier + 1
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let all_sources = checked.iter().map(|(_, sources, _)| *sources).collect();
+        for (line, _, dests) in checked.iter() {
+            let unmapped = IntervalSet::from_iter([*dests]).difference(&all_sources);
+            if let Some(unmapped) = unmapped.intervals().first() {
+                problems.push(AocError::at_line(
+                    *line,
+                    format!(
+                        "{}: the destination range {} overlaps {}, which no source range covers \
+                         and so maps to itself",
+                        name, dests, unmapped
+                    ),
+                ));
+            }
+        }
+        problems
+    }
+
+    /// builds the map, once [`MapSection::problems`] found nothing wrong with it
+    fn into_map(self) -> CategoryMap {
+        let pieces = self.ranges.iter().map(|r| Piece {
+            source: r.sources().unwrap(),
+            dest: r.dest,
+        });
+        CategoryMap {
+            source: self.source,
+            dest: self.dest,
+            ranges: PiecewiseMap::new(pieces).expect("overlapping sources were checked for"),
+        }
+    }
+}
+
+/// all the problems as one error, pointing at the first of them and listing the others
+fn combine(mut problems: Vec<AocError>) -> AocError {
+    problems.sort_by_key(|p| p.line);
+    let mut first = problems.remove(0);
+    for problem in problems {
+        first.message += &format!(
+            "\n  line {}: {}",
+            problem.line.unwrap_or(0),
+            problem.message
+        );
+    }
+    first
+}
+
+/// Maps that lead back to a category they started from, found with a depth first search that keeps
+/// the categories on its current path on a stack.
+fn find_cycle(maps: &[MapSection]) -> Option<Vec<&MapSection>> {
+    fn visit<'a>(
+        maps: &'a [MapSection],
+        category: &'a str,
+        on_stack: &mut Vec<&'a str>,
+        path: &mut Vec<&'a MapSection>,
+        done: &mut HashSet<&'a str>,
+    ) -> Option<Vec<&'a MapSection>> {
+        // the map on the path out of each category on the stack is at the same index
+        if let Some(start) = on_stack.iter().position(|&c| c == category) {
+            return Some(path[start..].to_vec());
+        }
+        if !done.insert(category) {
+            return None;
+        }
+        on_stack.push(category);
+        for map in maps.iter().filter(|m| m.source == category) {
+            path.push(map);
+            if let Some(cycle) = visit(maps, &map.dest, on_stack, path, done) {
+                return Some(cycle);
+            }
+            path.pop();
+        }
+        on_stack.pop();
+        None
+    }
+
+    let mut done = HashSet::new();
+    maps.iter()
+        .find_map(|m| visit(maps, &m.source, &mut vec![], &mut vec![], &mut done))
+}
+
+/// the seeds as `<start> <length>` pairs, each of them a range that is not empty
+fn seed_ranges(seeds: &[Spanned<usize>]) -> Result<Vec<Interval>, ParseError> {
+    let pairs = seeds.chunks_exact(2);
+    match pairs.remainder() {
+        [odd] => Err(ParseError {
+            span: odd.span.clone(),
+            message: format!(
+                "the seeds come in `<start> <length>` pairs, but {} has no length",
+                odd.value
+            ),
+        }),
+        _ => pairs
+            .map(|pair| {
+                let (start, len) = (&pair[0], &pair[1]);
+                let span = start.span.start..len.span.end;
+                match Interval::checked_with_len(start.value, len.value) {
+                    Some(range) if !range.is_empty() => Ok(range),
+                    Some(_) => Err(ParseError {
+                        span,
+                        message: format!("the seed range starting at {} is empty", start.value),
+                    }),
+                    None => Err(ParseError {
+                        span,
+                        message: format!(
+                            "the seed range {} + {} overflows",
+                            start.value, len.value
+                        ),
+                    }),
+                }
+            })
+            .collect(),
+    }
+}
+
+impl Almanac {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        let mut lines = input.lines().enumerate();
+        // parse the seeds line
+        let (idx, seed_line) = lines
+            .next()
+            .ok_or_else(|| AocError::new("the input is empty"))?;
+        let seeds = parse::after_label(seed_line, "seeds:")
+            .and_then(|seeds| seeds.integers::<usize>())
+            .map_err(|e| e.on_line(idx, seed_line))?;
+        if seeds.is_empty() {
+            return Err(AocError::at_line(idx, "expected at least one seed"));
+        }
+        let mut peekable_lines = lines.peekable();
+        let mut maps = Vec::<MapSection>::new();
+        while peekable_lines.peek().is_some() {
+            let map = MapSection::parse(&mut peekable_lines)?;
+            if maps
+                .iter()
+                .any(|m| m.source == map.source && m.dest == map.dest)
+            {
+                return Err(AocError::at_line(
+                    map.line,
+                    format!("a second map from `{}` to `{}`", map.source, map.dest),
+                ));
+            }
+            maps.push(map);
+        }
+
+        if let Some(cycle) = find_cycle(&maps) {
+            let categories = cycle.iter().map(|m| m.dest.as_str());
+            let categories = std::iter::once(cycle[0].source.as_str())
+                .chain(categories)
+                .collect::<Vec<_>>();
+            return Err(AocError::at_line(
+                cycle[cycle.len() - 1].line,
+                format!("the maps form a cycle: {}", categories.join(" -> ")),
+            ));
+        }
+
+        let problems = maps
+            .iter()
+            .flat_map(MapSection::problems)
+            .collect::<Vec<_>>();
+        if !problems.is_empty() {
+            return Err(combine(problems));
+        }
+
+        let maps = maps.into_iter().map(MapSection::into_map).collect();
+        let mut almanac = Almanac {
+            seeds,
+            seed_line: seed_line.to_string(),
+            maps,
+            seed_to_location: vec![],
+        };
+        almanac.seed_to_location = almanac.chain(SEEDS, LOCATIONS)?;
+        Ok(almanac)
+    }
+
+    /// The seeds read as `<start> <length>` pairs, as part 2 does. Part 1 reads them as single
+    /// seeds, so the pairs are only checked when part 2 asks for them.
+    pub fn seed_ranges(&self) -> Result<Vec<Interval>, AocError> {
+        seed_ranges(&self.seeds).map_err(|e| e.on_line(0, &self.seed_line))
+    }
+
+    /// The maps leading from one category to another, as indices into `maps` in the order they
+    /// are applied. There must be exactly one way.
+    fn chain(&self, from: &str, to: &str) -> Result<Vec<usize>, AocError> {
+        let mut found = vec![];
+        self.paths(from, to, &mut HashMap::new(), &mut vec![], &mut found);
+
+        let describe = |path: &[usize]| {
+            let categories = path.iter().map(|&i| self.maps[i].dest.as_str());
+            std::iter::once(from)
+                .chain(categories)
+                .collect::<Vec<_>>()
+                .join(" -> ")
+        };
+        match &found[..] {
+            [] => Err(AocError::new(format!(
+                "no chain of maps leads from `{}` to `{}`",
+                from, to
+            ))),
+            [path] => Ok(path.clone()),
+            [first, second, ..] => Err(AocError::new(format!(
+                "`{}` can be mapped to `{}` in more than one way: {} and {}",
+                from,
+                to,
+                describe(first),
+                describe(second)
+            ))),
+        }
+    }
+
+    /// Collects the first two ways from `category` to `to` into `found`. Only maps that lead to
+    /// `to` at all are followed, so this never wanders through the rest of the graph.
+    fn paths<'a>(
+        &'a self,
+        category: &'a str,
+        to: &str,
+        ways: &mut HashMap<&'a str, usize>,
+        path: &mut Vec<usize>,
+        found: &mut Vec<Vec<usize>>,
+    ) {
+        if category == to {
+            found.push(path.clone());
+            return;
+        }
+        for (i, map) in self.maps.iter().enumerate() {
+            if found.len() == 2 {
+                return;
+            }
+            if map.source == category && self.ways(&map.dest, to, ways) > 0 {
+                path.push(i);
+                self.paths(&map.dest, to, ways, path, found);
+                path.pop();
+            }
+        }
+    }
+
+    /// How many ways lead from `category` to `to`, counting no further than 2. There are no
+    /// cycles, so each category's count is found once and remembered in `ways`.
+    fn ways<'a>(
+        &'a self,
+        category: &'a str,
+        to: &str,
+        ways: &mut HashMap<&'a str, usize>,
+    ) -> usize {
+        if category == to {
+            return 1;
+        }
+        if let Some(&n) = ways.get(category) {
+            return n;
+        }
+        let n = self
+            .maps
+            .iter()
+            .filter(|m| m.source == category)
+            .map(|m| self.ways(&m.dest, to, ways))
+            .sum::<usize>()
+            .min(2);
+        ways.insert(category, n);
+        n
+    }
+
+    /// the maps of a chain, in order
+    fn maps_on<'a>(&'a self, chain: &'a [usize]) -> impl Iterator<Item = &'a CategoryMap> {
+        chain.iter().map(|&i| &self.maps[i])
+    }
+
+    /// the whole chain of maps from one category to another, folded into a single map
+    pub fn composed(&self, from: &str, to: &str) -> Result<PiecewiseMap, AocError> {
+        Ok(self
+            .maps_on(&self.chain(from, to)?)
+            .fold(PiecewiseMap::identity(), |composed, map| {
+                composed.compose(&map.ranges)
+            }))
+    }
+
+    /// maps a value from one category to another, through as many maps as it takes
+    pub fn map_value(&self, value: usize, from: &str, to: &str) -> Result<usize, AocError> {
+        Ok(self
+            .maps_on(&self.chain(from, to)?)
+            .fold(value, |value, map| map.ranges.get(value)))
+    }
+
+    /// follows a set of values from one category to another, recording them in every category on
+    /// the way, starting with `from` itself
+    pub fn trace(&self, values: IntervalSet, from: &str, to: &str) -> Result<Vec<Stage>, AocError> {
+        let pieces = values.intervals().iter().map(|&source| Piece {
+            source,
+            dest: source.start,
+        });
+        let mut stages = vec![Stage {
+            category: from.to_string(),
+            pieces: pieces.collect(),
+        }];
+        for map in self.maps_on(&self.chain(from, to)?) {
+            let pieces = stages[stages.len() - 1]
+                .pieces
+                .iter()
+                .flat_map(|piece| map.ranges.split(piece.dest_interval()))
+                .collect();
+            stages.push(Stage {
+                category: map.dest.clone(),
+                pieces,
+            });
+        }
+        Ok(stages)
+    }
+}
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = Almanac;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Almanac::parse(input)
+    }
+
+    fn part1(almanac: &Self::Input) -> usize {
+        almanac
+            .seeds
+            .iter()
+            .map(|seed| {
+                almanac
+                    .maps_on(&almanac.seed_to_location)
+                    .fold(seed.value, |value, map| map.ranges.get(value))
+            })
+            .min()
+            .unwrap()
+    }
+
+    fn part2(almanac: &Self::Input) -> usize {
+        let seeds = almanac
+            .seed_ranges()
+            .expect("the seed ranges are checked before part 2 runs")
+            .into_iter()
+            .collect::<IntervalSet>();
+
+        let locations = almanac
+            .maps_on(&almanac.seed_to_location)
+            .fold(seeds, |set, map| map.ranges.map_set(&set));
+
+        locations.min().unwrap()
+    }
+
+    fn check(almanac: &Self::Input, part: Part) -> Result<(), AocError> {
+        match part {
+            Part::One => Ok(()),
+            Part::Two => almanac.seed_ranges().map(|_| ()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::puzzle::Puzzle;
+
+    use super::*;
+
+    const ALMANAC: &str = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n98 50 2\n\n\
+                           soil-to-location map:\n0 50 10\n50 0 10\n";
+
+    #[test]
+    fn follows_the_chain_between_categories() {
+        let almanac = Almanac::parse(ALMANAC).unwrap();
+        assert_eq!(almanac.map_value(98, SEEDS, "soil").unwrap(), 50);
+        assert_eq!(almanac.map_value(98, SEEDS, LOCATIONS).unwrap(), 0);
+        assert_eq!(almanac.map_value(7, "soil", "soil").unwrap(), 7);
+
+        let err = almanac.map_value(1, "soil", SEEDS).unwrap_err();
+        assert_eq!(err.message, "no chain of maps leads from `soil` to `seed`");
+    }
+
+    #[test]
+    fn finds_the_one_path_through_the_graph() {
+        let branch = format!("{}\nseed-to-water map:\n10 0 5\n0 10 5\n", ALMANAC);
+        let almanac = Almanac::parse(&branch).unwrap();
+        assert_eq!(almanac.map_value(98, SEEDS, LOCATIONS).unwrap(), 0);
+        assert_eq!(almanac.map_value(3, SEEDS, "water").unwrap(), 13);
+
+        let shortcut = format!("{}\nseed-to-location map:\n0 0 5\n", ALMANAC);
+        let err = Almanac::parse(&shortcut).unwrap_err();
+        assert_eq!(
+            err.message,
+            "`seed` can be mapped to `location` in more than one way: \
+             seed -> soil -> location and seed -> location"
+        );
+
+        let twice = format!("{}\nseed-to-soil map:\n0 0 5\n", ALMANAC);
+        assert_eq!(Almanac::parse(&twice).unwrap_err().line, Some(11));
+    }
+
+    #[test]
+    fn rejects_cycles() {
+        let cycle = ALMANAC.replace("soil-to-location", "soil-to-seed");
+        let err = Almanac::parse(&cycle).unwrap_err();
+        assert_eq!(err.line, Some(7));
+        assert_eq!(err.message, "the maps form a cycle: seed -> soil -> seed");
+
+        // the way from `seed` to `location` is still the only one, but it goes past a cycle
+        let beside = format!(
+            "{}\nlocation-to-water map:\n0 0 1\n\nwater-to-soil map:\n0 0 1\n",
+            ALMANAC
+        );
+        let err = Almanac::parse(&beside).unwrap_err();
+        assert_eq!(err.line, Some(14));
+        assert_eq!(
+            err.message,
+            "the maps form a cycle: soil -> location -> water -> soil"
+        );
+    }
+
+    #[test]
+    fn rejects_missing_chains() {
+        let missing = ALMANAC.replace("soil-to-location", "soil-to-water");
+        let err = Almanac::parse(&missing).unwrap_err();
+        assert_eq!(
+            err.message,
+            "no chain of maps leads from `seed` to `location`"
+        );
+
+        // every category maps to every later one, so there are 2^38 ways from c0 to c39 and
+        // listing them all to find that none leads to `seed` would never finish
+        let mut dense = format!("{}\nlocation-to-c0 map:\n0 0 1\n", ALMANAC);
+        for i in 0..40 {
+            for j in i + 1..40 {
+                dense += &format!("\nc{}-to-c{} map:\n0 0 1\n", i, j);
+            }
+        }
+        let almanac = Almanac::parse(&dense).unwrap();
+        assert!(almanac.map_value(1, "c0", SEEDS).is_err());
+        assert_eq!(almanac.map_value(1, "c0", "c1").unwrap(), 1);
+        assert!(almanac.map_value(1, "c0", "c39").is_err());
+    }
+
+    fn example() -> Almanac {
+        let puzzle = Puzzle::parse(include_str!("../puzzle.md"));
+        Almanac::parse(&puzzle.example(Part::One).unwrap().input).unwrap()
+    }
+
+    #[test]
+    fn traces_values_through_every_category() {
+        let almanac = example();
+        let seeds = IntervalSet::from_iter([Interval::new(79, 93)]);
+        let stages = almanac.trace(seeds, SEEDS, LOCATIONS).unwrap();
+        let categories = stages
+            .iter()
+            .map(|s| s.category.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            categories,
+            [
+                SEEDS,
+                "soil",
+                "fertilizer",
+                "water",
+                "light",
+                "temperature",
+                "humidity",
+                LOCATIONS
+            ]
+        );
+        // the range is split where it crosses the edges of the maps' ranges
+        let locations = stages.last().unwrap().values();
+        let locations = locations.intervals();
+        assert_eq!(
+            locations,
+            [
+                Interval::new(46, 56),
+                Interval::new(60, 61),
+                Interval::new(82, 85)
+            ]
+        );
+
+        let seed = IntervalSet::from_iter([Interval::with_len(79, 1)]);
+        let stages = almanac.trace(seed, SEEDS, "soil").unwrap();
+        assert_eq!(stages[1].values().min(), Some(81));
+
+        // seed-to-soil cuts this at 50 and 98, and the pieces land next to each other
+        let seeds = IntervalSet::from_iter([Interval::new(0, 100)]);
+        let stages = almanac.trace(seeds, SEEDS, "soil").unwrap();
+        let pieces = stages[1]
+            .pieces
+            .iter()
+            .map(|p| (p.source, p.dest_interval()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            pieces,
+            [
+                (Interval::new(0, 50), Interval::new(0, 50)),
+                (Interval::new(50, 98), Interval::new(52, 100)),
+                (Interval::new(98, 100), Interval::new(50, 52)),
+            ]
+        );
+        assert_eq!(stages[1].values().intervals(), [Interval::new(0, 100)]);
+    }
+
+    #[test]
+    fn composition_matches_the_chain() {
+        let almanac = Almanac::parse(ALMANAC).unwrap();
+        let composed = almanac.composed(SEEDS, LOCATIONS).unwrap();
+        assert_eq!(
+            composed.to_string(),
+            "0..10 -> 50..60\n50..52 -> 98..100\n52..60 -> 2..10\n98..100 -> 0..2\n"
+        );
+
+        let almanac = example();
+        let composed = almanac.composed(SEEDS, LOCATIONS).unwrap();
+        for seed in 0..200 {
+            assert_eq!(
+                composed.get(seed),
+                almanac.map_value(seed, SEEDS, LOCATIONS).unwrap()
+            );
+        }
+        assert_eq!(composed.get(82), 46);
+    }
+
+    #[test]
+    fn inverse_maps_back_to_the_sources() {
+        let almanac = Almanac::parse(ALMANAC).unwrap();
+        let inverse = almanac.maps[0].ranges.invert().unwrap();
+        assert_eq!(inverse.get(50), 98);
+        assert_eq!(inverse.get(7), 7);
+
+        let composed = example().composed(SEEDS, LOCATIONS).unwrap();
+        let inverse = composed.invert().unwrap();
+        assert_eq!(inverse.get(46), 82);
+    }
+
+    /// every seed of part 2 mapped on its own, in sorted batches through the whole chain folded
+    /// into one map
+    fn brute_force_part2(almanac: &Almanac) -> usize {
+        const BATCH: usize = 1 << 16;
+        let seed_to_location = almanac.composed(SEEDS, LOCATIONS).unwrap();
+        let mut batch = Vec::with_capacity(BATCH);
+        let mut lowest = usize::MAX;
+        for seed_range in almanac.seed_ranges().unwrap() {
+            for start in (seed_range.start..seed_range.end).step_by(BATCH) {
+                batch.clear();
+                batch.extend(start..seed_range.end.min(start + BATCH));
+                let locations = seed_to_location.get_sorted(&batch);
+                lowest = lowest.min(locations.into_iter().min().unwrap());
+            }
+        }
+        lowest
+    }
+
+    #[test]
+    fn brute_force_matches_part2_on_the_example() {
+        let almanac = example();
+        assert_eq!(brute_force_part2(&almanac), Day05::part2(&almanac));
+    }
+
+    /// goes through billions of seeds, so it is only practical with `--release`
+    #[test]
+    #[ignore]
+    fn brute_force_matches_part2() {
+        let input = std::fs::read_to_string(common::input::day_dir(Day05::DAY).join("input"))
+            .expect("the puzzle input is needed for this test");
+        let almanac = Almanac::parse(&input).unwrap();
+        assert_eq!(brute_force_part2(&almanac), Day05::part2(&almanac));
+    }
+
+    #[test]
+    fn reads_the_seeds_as_ranges() {
+        let almanac = |seeds: &str| Almanac::parse(&ALMANAC.replace("seeds: 79 14", seeds));
+        let err = |seeds: &str| almanac(seeds).unwrap().seed_ranges().unwrap_err();
+        let odd = err("seeds: 79 14 55");
+        assert_eq!((odd.line, odd.column), (Some(1), Some(14)));
+        assert_eq!(
+            odd.message,
+            "the seeds come in `<start> <length>` pairs, but 55 has no length"
+        );
+        let overflow = err("seeds: 18446744073709551615 2");
+        assert_eq!(
+            overflow.message,
+            "the seed range 18446744073709551615 + 2 overflows"
+        );
+        assert_eq!(err("seeds: 79 0").line, Some(1));
+        let none = almanac("seeds:").unwrap_err();
+        assert_eq!(none.message, "expected at least one seed");
+
+        // part 1 reads the seeds one by one, so it does not mind them
+        let odd = almanac("seeds: 79 14 55").unwrap();
+        assert_eq!(Day05::part1(&odd), 5);
+        assert!(Day05::check(&odd, Part::One).is_ok());
+        assert!(Day05::check(&odd, Part::Two).is_err());
+
+        let almanac = Almanac::parse(ALMANAC).unwrap();
+        assert_eq!(almanac.seed_ranges().unwrap(), [Interval::new(79, 93)]);
+    }
+
+    #[test]
+    fn reports_every_problem_in_the_maps() {
+        let bad = ALMANAC
+            .replace("50 98 2", "50 98 2\n0 90 10\n51 0 3")
+            .replace("0 50 10", "0 50 10\n0 18446744073709551615 2");
+        let err = Almanac::parse(&bad).unwrap_err();
+        assert_eq!(err.line, Some(5));
+        assert_eq!(
+            err.message,
+            "seed-to-soil map: the source range 90..100 overlaps 98..100 on line 4\n  \
+             line 5: seed-to-soil map: the destination range 0..10 overlaps 3..10, which no \
+             source range covers and so maps to itself\n  \
+             line 6: seed-to-soil map: the destination range 51..54 overlaps 50..52 on line 4\n  \
+             line 6: seed-to-soil map: the destination range 51..54 overlaps 52..54, which no \
+             source range covers and so maps to itself\n  \
+             line 11: soil-to-location map: the source range 18446744073709551615 + 2 overflows"
+        );
+    }
+
+    #[test]
+    fn rejects_maps_that_are_not_one_to_one() {
+        // 98 and 99 map onto 50 and 51, which no range covers, so they also map to themselves
+        let almanac =
+            "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n\nsoil-to-location map:\n0 50 10\n";
+        let err = Almanac::parse(almanac).unwrap_err();
+        assert_eq!(
+            err.message,
+            "seed-to-soil map: the destination range 50..52 overlaps 50..52, which no source \
+             range covers and so maps to itself\n  \
+             line 7: soil-to-location map: the destination range 0..10 overlaps 0..10, which no \
+             source range covers and so maps to itself"
+        );
+        assert_eq!(err.line, Some(4));
+    }
+}
@@ -0,0 +1,3 @@
+use day05::Day05;
+
+common::example_tests!(Day05: part1, part2);
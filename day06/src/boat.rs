@@ -0,0 +1,221 @@
+//! How far a boat goes for each way of holding its button, and which holds beat a record.
+//!
+//! Every model's distance grows up to its best hold and shrinks after it, so the winning holds
+//! are always one interval and can be found by binary search on either side of the best hold.
+//! Models whose distance is a quadratic in the hold time solve for the interval directly.
+use std::ops::RangeInclusive;
+
+pub trait BoatModel {
+    /// how far the boat goes in a race of `time` when the button is held for `hold`, or `None`
+    /// if the distance does not fit in a `u128` (which beats every record)
+    fn distance(&self, time: u128, hold: u128) -> Option<u128>;
+
+    /// a hold that goes furthest, the distance never shrinks before it and never grows after it
+    fn best_hold(&self, time: u128) -> u128;
+
+    fn beats(&self, time: u128, hold: u128, record: u128) -> bool {
+        self.distance(time, hold)
+            .is_none_or(|distance| distance > record)
+    }
+
+    /// the hold times that beat the record, as `first..=last`
+    fn winning_holds(&self, time: u128, record: u128) -> Option<RangeInclusive<u128>> {
+        search_winning_holds(self, time, record)
+    }
+
+    fn ways_to_win(&self, time: u128, record: u128) -> u128 {
+        match self.winning_holds(time, record) {
+            Some(holds) => holds.end() - holds.start() + 1,
+            None => 0,
+        }
+    }
+}
+
+/// the first value in `low..high` where `pred` stops holding, or `high` if it always holds
+fn partition_point(mut low: u128, mut high: u128, pred: impl Fn(u128) -> bool) -> u128 {
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match pred(mid) {
+            true => low = mid + 1,
+            false => high = mid,
+        }
+    }
+    low
+}
+
+/// finds the winning holds by binary search on both sides of the best hold
+pub fn search_winning_holds<M: BoatModel + ?Sized>(
+    model: &M,
+    time: u128,
+    record: u128,
+) -> Option<RangeInclusive<u128>> {
+    let best = model.best_hold(time);
+    if !model.beats(time, best, record) {
+        return None;
+    }
+    let first = partition_point(0, best, |hold| !model.beats(time, hold, record));
+    let last = match model.beats(time, time, record) {
+        true => time,
+        false => partition_point(best, time, |hold| model.beats(time, hold, record)) - 1,
+    };
+    Some(first..=last)
+}
+
+/// The puzzle's model: every millisecond of holding adds one millimetre per millisecond of speed.
+#[derive(Debug, Clone, Copy)]
+pub struct Linear;
+
+impl BoatModel for Linear {
+    fn distance(&self, time: u128, hold: u128) -> Option<u128> {
+        hold.checked_mul(time.saturating_sub(hold))
+    }
+
+    fn best_hold(&self, time: u128) -> u128 {
+        time / 2
+    }
+
+    /// A hold of `h` goes `h * (time - h)`, so the winning holds lie strictly between the roots of
+    /// `h^2 - time * h + record`. The integer square root can be off by one from the real root,
+    /// which is corrected by checking the boundary. The holds are symmetric around `time / 2`, so
+    /// the last one is `time - first`.
+    fn winning_holds(&self, time: u128, record: u128) -> Option<RangeInclusive<u128>> {
+        if !self.beats(time, time / 2, record) {
+            return None;
+        }
+
+        let discriminant = time
+            .checked_mul(time)
+            .and_then(|square| square.checked_sub(record.checked_mul(4)?));
+        let Some(discriminant) = discriminant else {
+            // the square does not fit, so fall back to searching
+            return search_winning_holds(self, time, record);
+        };
+        let mut first = (time - discriminant.isqrt()) / 2;
+        while !self.beats(time, first, record) {
+            first += 1;
+        }
+        while first > 0 && self.beats(time, first - 1, record) {
+            first -= 1;
+        }
+        Some(first..=time - first)
+    }
+}
+
+/// Every millisecond of holding adds `per_ms` millimetres per millisecond of speed.
+#[derive(Debug, Clone, Copy)]
+pub struct Acceleration {
+    pub per_ms: u128,
+}
+
+impl BoatModel for Acceleration {
+    fn distance(&self, time: u128, hold: u128) -> Option<u128> {
+        Linear.distance(time, hold)?.checked_mul(self.per_ms)
+    }
+
+    fn best_hold(&self, time: u128) -> u128 {
+        time / 2
+    }
+
+    /// `per_ms * d > record` exactly when `d > record / per_ms`, rounding down, which is the
+    /// linear model with a shorter record
+    fn winning_holds(&self, time: u128, record: u128) -> Option<RangeInclusive<u128>> {
+        match self.per_ms {
+            0 => None,
+            per_ms => Linear.winning_holds(time, record / per_ms),
+        }
+    }
+}
+
+/// The linear model, except that the speed stops growing at `cap` millimetres per millisecond.
+#[derive(Debug, Clone, Copy)]
+pub struct ChargeCap {
+    pub cap: u128,
+}
+
+impl BoatModel for ChargeCap {
+    fn distance(&self, time: u128, hold: u128) -> Option<u128> {
+        hold.min(self.cap).checked_mul(time.saturating_sub(hold))
+    }
+
+    fn best_hold(&self, time: u128) -> u128 {
+        // holding past the cap only loses time
+        self.cap.min(time / 2)
+    }
+}
+
+/// The linear model, except that the boat only starts moving `delay` milliseconds after the
+/// button is released.
+#[derive(Debug, Clone, Copy)]
+pub struct StartupDelay {
+    pub delay: u128,
+}
+
+impl BoatModel for StartupDelay {
+    fn distance(&self, time: u128, hold: u128) -> Option<u128> {
+        Linear.distance(time.saturating_sub(self.delay), hold)
+    }
+
+    fn best_hold(&self, time: u128) -> u128 {
+        time.saturating_sub(self.delay) / 2
+    }
+
+    /// the delay is lost from the race, so this is a linear race that is `delay` shorter
+    fn winning_holds(&self, time: u128, record: u128) -> Option<RangeInclusive<u128>> {
+        Linear.winning_holds(time.saturating_sub(self.delay), record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the original solution, trying every hold time
+    fn ways_to_win_iterative(model: &dyn BoatModel, time: u128, record: u128) -> u128 {
+        (0..=time)
+            .filter(|&hold| model.beats(time, hold, record))
+            .count() as u128
+    }
+
+    fn models() -> Vec<Box<dyn BoatModel>> {
+        vec![
+            Box::new(Linear),
+            Box::new(Acceleration { per_ms: 3 }),
+            Box::new(Acceleration { per_ms: 0 }),
+            Box::new(ChargeCap { cap: 7 }),
+            Box::new(StartupDelay { delay: 5 }),
+        ]
+    }
+
+    #[test]
+    fn every_model_matches_trying_every_hold() {
+        for model in models() {
+            for time in 0..60 {
+                for record in 0..=time * time + 1 {
+                    let expected = ways_to_win_iterative(model.as_ref(), time, record);
+                    assert_eq!(model.ways_to_win(time, record), expected);
+                    let searched = search_winning_holds(model.as_ref(), time, record);
+                    assert_eq!(searched, model.winning_holds(time, record));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn finds_the_boundary_of_huge_races() {
+        for (time, record) in [
+            (71530, 940200),
+            (u64::MAX as u128, 12345678901234567890),
+            (1 << 100, 1 << 120),
+            (u128::MAX, u128::MAX),
+            (u128::MAX, 0),
+        ] {
+            let holds = Linear.winning_holds(time, record).unwrap();
+            let (first, last) = (*holds.start(), *holds.end());
+            assert!(Linear.beats(time, first, record) && Linear.beats(time, last, record));
+            assert!(first == 0 || !Linear.beats(time, first - 1, record));
+            assert!(last == time || !Linear.beats(time, last + 1, record));
+            assert_eq!(Some(holds), search_winning_holds(&Linear, time, record));
+        }
+        assert_eq!(Linear.winning_holds(30, 225), None);
+    }
+}
@@ -0,0 +1,293 @@
+use std::{fmt, iter::zip, num::IntErrorKind, str::FromStr};
+
+use common::{
+    parse::{self, Spanned},
+    AocError, Solution,
+};
+
+pub mod boat;
+
+use boat::{BoatModel, Linear};
+
+pub struct Day06;
+
+#[derive(Debug, Clone)]
+pub struct Race {
+    time: u128,
+    record_distance: u128,
+}
+
+impl Race {
+    /// how many hold times beat the record under the given model
+    pub fn ways_to_win(&self, model: &dyn BoatModel) -> u128 {
+        model.ways_to_win(self.time, self.record_distance)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Records {
+    races: Vec<Race>,
+}
+
+impl Records {
+    /// the product of the number of ways to win each race under the given model
+    pub fn margin_of_error(&self, model: &dyn BoatModel) -> u128 {
+        self.races
+            .iter()
+            .map(|race| race.ways_to_win(model))
+            .product()
+    }
+}
+
+/// What can be wrong with the sheet of races. Lines are 0-based indexes into the sheet, they
+/// become line numbers once the error is turned into an [`AocError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SheetError {
+    /// the sheet has no line starting with `label` where one was expected
+    MissingLine { line: usize, label: &'static str },
+    /// the line has its label but no numbers after it
+    NoNumbers { line: usize, label: &'static str },
+    /// a token after the label is not a number
+    NotANumber {
+        line: usize,
+        column: usize,
+        token: String,
+    },
+    /// the sheet lists a different number of times than distances
+    CountMismatch { times: usize, distances: usize },
+    /// the number, possibly joined from several tokens, does not fit in a `u128`; `column` is
+    /// where its first token starts
+    Overflow {
+        line: usize,
+        column: usize,
+        digits: String,
+    },
+}
+
+impl fmt::Display for SheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SheetError::MissingLine { label, .. } => write!(f, "expected a `{}` line", label),
+            SheetError::NoNumbers { label, .. } => write!(f, "expected numbers after `{}`", label),
+            SheetError::NotANumber { token, .. } => write!(f, "{:?} is not a number", token),
+            SheetError::CountMismatch { times, distances } => write!(
+                f,
+                "the sheet has {} times but {} distances",
+                times, distances
+            ),
+            SheetError::Overflow { digits, .. } => {
+                write!(f, "{} is too large for a race", digits)
+            }
+        }
+    }
+}
+
+impl From<SheetError> for AocError {
+    fn from(e: SheetError) -> Self {
+        let message = e.to_string();
+        match e {
+            SheetError::MissingLine { line, .. } | SheetError::NoNumbers { line, .. } => {
+                AocError::at_line(line, message)
+            }
+            SheetError::NotANumber { line, column, .. }
+            | SheetError::Overflow { line, column, .. } => AocError {
+                column: Some(column),
+                ..AocError::at_line(line, message)
+            },
+            SheetError::CountMismatch { .. } => AocError::new(message),
+        }
+    }
+}
+
+/// the tokens following the label of line `idx`
+fn labelled_tokens<'a>(
+    idx: usize,
+    line: Option<&'a str>,
+    label: &'static str,
+) -> Result<(&'a str, Vec<Spanned<&'a str>>), SheetError> {
+    let missing = SheetError::MissingLine { line: idx, label };
+    let line = line.ok_or(missing.clone())?;
+    let tokens = parse::after_label(line, label)
+        .map_err(|_| missing)?
+        .tokens();
+    match tokens.is_empty() {
+        true => Err(SheetError::NoNumbers { line: idx, label }),
+        false => Ok((line, tokens)),
+    }
+}
+
+/// checks that every token is plain digits, since `u128` parsing also takes a leading `+`
+fn plain_digits(idx: usize, line: &str, tokens: &[Spanned<&str>]) -> Result<(), SheetError> {
+    match tokens
+        .iter()
+        .find(|t| !t.value.chars().all(|c| c.is_ascii_digit()))
+    {
+        Some(t) => Err(SheetError::NotANumber {
+            line: idx,
+            column: line[..t.span.start].chars().count() + 1,
+            token: t.value.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// parses `digits`, which start at byte `start` of `line`
+fn parse_number(idx: usize, line: &str, digits: &str, start: usize) -> Result<u128, SheetError> {
+    let column = line[..start].chars().count() + 1;
+    digits.parse::<u128>().map_err(|e| match e.kind() {
+        IntErrorKind::PosOverflow => SheetError::Overflow {
+            line: idx,
+            column,
+            digits: digits.to_string(),
+        },
+        _ => SheetError::NotANumber {
+            line: idx,
+            column,
+            token: digits.to_string(),
+        },
+    })
+}
+
+impl FromStr for Records {
+    type Err = SheetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let parse_line = |idx, line, label| {
+            let (line, tokens) = labelled_tokens(idx, line, label)?;
+            plain_digits(idx, line, &tokens)?;
+            tokens
+                .iter()
+                .map(|t| parse_number(idx, line, t.value, t.span.start))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let times = parse_line(0, lines.next(), "Time:")?;
+        let distances = parse_line(1, lines.next(), "Distance:")?;
+        if times.len() != distances.len() {
+            return Err(SheetError::CountMismatch {
+                times: times.len(),
+                distances: distances.len(),
+            });
+        }
+
+        let races = zip(times, distances)
+            .map(|(time, record_distance)| Race {
+                time,
+                record_distance,
+            })
+            .collect();
+
+        Ok(Records { races })
+    }
+}
+
+impl FromStr for Race {
+    type Err = SheetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        // the numbers are one value with bad kerning, so join the digits back together
+        let parse_line = |idx, line, label| {
+            let (line, tokens) = labelled_tokens(idx, line, label)?;
+            plain_digits(idx, line, &tokens)?;
+            let digits = tokens.iter().map(|t| t.value).collect::<String>();
+            parse_number(idx, line, &digits, tokens[0].span.start)
+        };
+        let time = parse_line(0, lines.next(), "Time:")?;
+        let distance = parse_line(1, lines.next(), "Distance:")?;
+
+        Ok(Self {
+            record_distance: distance,
+            time,
+        })
+    }
+}
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    // part 1 reads the sheet as separate races, part 2 reads it as one race with bad kerning
+    type Input = (Records, Race);
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok((input.parse::<Records>()?, input.parse::<Race>()?))
+    }
+
+    fn part1((records, _): &Self::Input) -> u128 {
+        records.margin_of_error(&Linear)
+    }
+
+    fn part2((_, race): &Self::Input) -> u128 {
+        race.ways_to_win(&Linear)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_what_is_wrong_with_the_sheet() {
+        let err = |sheet: &str| sheet.parse::<Records>().unwrap_err();
+        assert_eq!(
+            err("Time: 7 15\n"),
+            SheetError::MissingLine {
+                line: 1,
+                label: "Distance:"
+            }
+        );
+        assert_eq!(
+            err("Time: 7 x5\nDistance: 9 40"),
+            SheetError::NotANumber {
+                line: 0,
+                column: 9,
+                token: "x5".to_string()
+            }
+        );
+        assert_eq!(
+            err("Time: 7 15 30\nDistance: 9 40"),
+            SheetError::CountMismatch {
+                times: 3,
+                distances: 2
+            }
+        );
+        assert_eq!(
+            err("Time:\nDistance: 9"),
+            SheetError::NoNumbers {
+                line: 0,
+                label: "Time:"
+            }
+        );
+
+        let signed = "Time: 7 +15 30\nDistance: 9 40 200";
+        let plus = SheetError::NotANumber {
+            line: 0,
+            column: 9,
+            token: "+15".to_string(),
+        };
+        assert_eq!(signed.parse::<Race>().unwrap_err(), plus);
+        assert_eq!(err(signed), plus);
+
+        let huge = "Time: 7 15\nDistance: 340282366920938463463 374607431768211456";
+        assert!(huge.parse::<Records>().is_ok());
+        assert_eq!(
+            huge.parse::<Race>().unwrap_err(),
+            SheetError::Overflow {
+                line: 1,
+                column: 11,
+                digits: "340282366920938463463374607431768211456".to_string()
+            }
+        );
+        let err = AocError::from(huge.parse::<Race>().unwrap_err());
+        assert_eq!((err.line, err.column), (Some(2), Some(11)));
+    }
+
+    #[test]
+    fn reads_sheets_with_crlf_line_endings() {
+        let sheet = "Time:      7  15   30\r\nDistance:  9  40  200\r\n";
+        assert_eq!(Day06::part1(&Day06::parse(sheet).unwrap()), 288);
+        assert_eq!(Day06::part2(&Day06::parse(sheet).unwrap()), 71503);
+    }
+}
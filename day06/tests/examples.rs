@@ -0,0 +1,3 @@
+use day06::Day06;
+
+common::example_tests!(Day06: part1, part2);
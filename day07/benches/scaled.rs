@@ -0,0 +1,63 @@
+//! Ranks generated bid lists of growing size, to check that the time per hand stays flat.
+//!
+//! Run with `cargo bench -p day07`, optionally followed by `-- <hands>...` to pick the sizes.
+use std::{env, time::Duration};
+
+use common::bench::measure;
+use day07::{odds::Tournament, parse_bids, total_winnings, CamelRules};
+
+const DEFAULT_SIZES: [usize; 4] = [1_000, 100_000, 1_000_000, 10_000_000];
+
+/// `hands` lines of `<hand> <bid>`, the same ones on every run
+fn bid_file(hands: usize) -> String {
+    let tournament = Tournament {
+        hands,
+        max_bid: 1000,
+        rounds: 1,
+        seed: 0x9e37_79b9_7f4a_7c15,
+    };
+    tournament
+        .bid_files(&CamelRules::standard())
+        .next()
+        .unwrap()
+}
+
+fn per_hand(time: Duration, hands: usize) -> String {
+    format!("{:.1} ns/hand", time.as_nanos() as f64 / hands as f64)
+}
+
+fn main() {
+    let sizes = env::args()
+        .skip(1)
+        .filter_map(|arg| arg.parse::<usize>().ok())
+        .collect::<Vec<_>>();
+    let sizes = match sizes.is_empty() {
+        true => DEFAULT_SIZES.to_vec(),
+        false => sizes,
+    };
+
+    println!(
+        "{:>10}  {:>8}  {:>5}  {:>16}  {:>16}",
+        "hands", "rules", "runs", "parse (median)", "rank (median)"
+    );
+    for hands in sizes {
+        let file = bid_file(hands);
+        let iterations = (10_000_000 / hands.max(1)).clamp(3, 100);
+        for (name, rules) in [
+            ("standard", CamelRules::standard()),
+            ("jokers", CamelRules::jokers()),
+        ] {
+            let parse = measure(iterations, || parse_bids(&file, &rules).unwrap());
+            let bids = parse_bids(&file, &rules).unwrap();
+            let rank = measure(iterations, || total_winnings(&bids));
+            println!(
+                "{:>10}  {:>8}  {:>5}  {:>16}  {:>16}",
+                hands,
+                name,
+                iterations,
+                per_hand(parse.median, hands),
+                per_hand(rank.median, hands)
+            );
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Why each hand ranks where it does: what its wild cards count as, the type that makes, and what
+//! its place in the ranking wins.
+use crate::{CamelRules, Card, Hand, HandType};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// the 1-based line of the bid
+    pub line: usize,
+    pub hand: String,
+    /// the hand with every wild card replaced by the card it counts as
+    pub played_as: String,
+    pub hand_type: HandType,
+    /// 1 for the weakest hand
+    pub rank: usize,
+    pub bid: usize,
+    /// the bid multiplied by the rank
+    pub winnings: usize,
+}
+
+impl Hand {
+    /// The card every wild card counts as: the one in the largest group, the strongest of them if
+    /// several are as large, or the strongest card that is not wild if the whole hand is.
+    fn substitute(&self, rules: &CamelRules) -> Card {
+        let mut ranks = self
+            .cards
+            .iter()
+            .filter(|c| !c.wild)
+            .map(|c| c.rank)
+            .collect::<Vec<_>>();
+        ranks.sort_unstable();
+        let rank = match ranks
+            .chunk_by(|a, b| a == b)
+            .max_by_key(|g| (g.len(), g[0]))
+        {
+            Some(group) => group[0],
+            None => (0..rules.order.len())
+                .rev()
+                .find(|&r| !rules.wild.contains(&rules.order[r]))
+                .unwrap_or(rules.order.len() - 1) as u8,
+        };
+        Card { rank, wild: false }
+    }
+
+    /// the hand as written, under the rules it was read with
+    pub fn labels(&self, rules: &CamelRules) -> String {
+        self.cards.iter().map(|&c| rules.label(c)).collect()
+    }
+
+    /// the hand with every wild card replaced by the card that makes its type strongest
+    pub fn played_as(&self, rules: &CamelRules) -> String {
+        let substitute = self.substitute(rules);
+        self.cards
+            .iter()
+            .map(|&c| match c.wild {
+                true => rules.label(substitute),
+                false => rules.label(c),
+            })
+            .collect()
+    }
+}
+
+/// every bid from the weakest hand to the strongest, with what it counts as and what it wins
+pub fn explain(bids: &[(Hand, usize)], rules: &CamelRules) -> Vec<Explanation> {
+    let mut ranking = (0..bids.len()).collect::<Vec<_>>();
+    // the same order `total_winnings` ranks the bids in
+    ranking.sort_unstable_by_key(|&i| (bids[i].0.key, bids[i].1));
+    ranking
+        .into_iter()
+        .enumerate()
+        .map(|(rank, i)| {
+            let (hand, bid) = &bids[i];
+            Explanation {
+                line: i + 1,
+                hand: hand.labels(rules),
+                played_as: hand.played_as(rules),
+                hand_type: hand.hand_type(),
+                rank: rank + 1,
+                bid: *bid,
+                winnings: (rank + 1) * bid,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_bids, test_support::every_hand, total_winnings};
+
+    #[test]
+    fn explains_the_example_with_jokers() {
+        let rules = CamelRules::jokers();
+        let bids = parse_bids(
+            "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\nJJJJJ 1",
+            &rules,
+        )
+        .unwrap();
+        let explained = explain(&bids, &rules);
+        let rows = explained
+            .iter()
+            .map(|e| (e.hand.as_str(), e.played_as.as_str(), e.rank, e.winnings))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            [
+                ("32T3K", "32T3K", 1, 765),
+                ("KK677", "KK677", 2, 56),
+                ("T55J5", "T5555", 3, 2052),
+                ("QQQJA", "QQQQA", 4, 1932),
+                ("KTJJT", "KTTTT", 5, 1100),
+                ("JJJJJ", "AAAAA", 6, 6),
+            ]
+        );
+        assert_eq!(explained[4].hand_type.to_string(), "four of a kind");
+        assert_eq!(explained[4].line, 4);
+        let total = explained.iter().map(|e| e.winnings).sum::<usize>();
+        assert_eq!(total, total_winnings(&bids));
+    }
+
+    #[test]
+    fn substitutions_make_the_same_type_without_wild_cards() {
+        let labels = ['2', '3', '9', 'J', 'Q'];
+        let wild = CamelRules::new("23456789TJQKA", "J3").unwrap();
+        let plain = CamelRules::new("23456789TJQKA", "").unwrap();
+        for hand in every_hand(&labels, 5) {
+            let hand = Hand::parse(0, &hand, &hand, &wild).unwrap();
+            let played_as = hand.played_as(&wild);
+            let played = Hand::parse(0, &played_as, &played_as, &plain).unwrap();
+            assert_eq!(played.hand_type(), hand.hand_type(), "{}", played_as);
+        }
+    }
+}
@@ -0,0 +1,530 @@
+use std::fmt;
+
+use common::{AocError, Solution};
+
+pub mod explain;
+pub mod odds;
+#[cfg(test)]
+mod test_support;
+
+pub struct Day07;
+
+/// The most cards a hand can have, whatever the labels. Every card takes up at least one bit of
+/// the sort key, unless a single label is ranked, and this also bounds the table of types.
+pub const MAX_HAND_SIZE: usize = u128::BITS as usize;
+
+/// How the cards of a hand are ranked, which of them are wild and how many make a hand. A wild
+/// card counts as whichever card makes the hand's type strongest, but keeps its own rank when
+/// breaking ties.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CamelRules {
+    /// the card labels from weakest to strongest
+    order: Vec<char>,
+    wild: Vec<char>,
+    hand_size: usize,
+    /// `types[n][k]`: how many types a hand of `n` cards can have with groups of at most `k`
+    types: Vec<Vec<u128>>,
+}
+
+impl CamelRules {
+    /// `order` lists the card labels from weakest to strongest, `wild` the ones that are wild,
+    /// for hands of five cards
+    pub fn new(order: &str, wild: &str) -> Result<Self, String> {
+        let order = order.chars().collect::<Vec<_>>();
+        if let Some(c) = order
+            .iter()
+            .find(|c| order.iter().filter(|o| o == c).count() > 1)
+        {
+            return Err(format!("card {:?} is ranked twice", c));
+        }
+        if order.len() > 1 << u8::BITS {
+            return Err(format!(
+                "{} cards are ranked, at most 256 can be",
+                order.len()
+            ));
+        }
+        let wild = wild.chars().collect::<Vec<_>>();
+        if let Some(c) = wild.iter().find(|c| !order.contains(c)) {
+            return Err(format!("wild card {:?} is not ranked", c));
+        }
+        CamelRules {
+            order,
+            wild,
+            hand_size: 0,
+            types: vec![],
+        }
+        .with_hand_size(5)
+    }
+
+    /// the same rules for hands of `size` cards, as long as their sort keys fit in a `u128`
+    pub fn with_hand_size(self, size: usize) -> Result<Self, String> {
+        let too_long = || {
+            format!(
+                "hands of {} cards are too long to rank with {} card labels",
+                size,
+                self.order.len()
+            )
+        };
+        if size == 0 {
+            return Err("a hand needs at least one card".to_string());
+        }
+        if size > MAX_HAND_SIZE {
+            return Err(format!(
+                "hands of {} cards are too long, at most {} cards can be ranked",
+                size, MAX_HAND_SIZE
+            ));
+        }
+        if size.saturating_mul(self.rank_bits() as usize) > u128::BITS as usize {
+            return Err(too_long());
+        }
+        let mut types = vec![vec![0u128; size + 1]; size + 1];
+        for n in 0..=size {
+            for k in 0..=size {
+                types[n][k] = match (n, k) {
+                    (0, _) => 1,
+                    (_, 0) => 0,
+                    // either no group has `k` cards, or one does and the rest are split up
+                    _ if k <= n => types[n][k - 1] + types[n - k][k],
+                    _ => types[n][k - 1],
+                };
+            }
+        }
+        let type_bits = u128::BITS - (types[size][size] - 1).leading_zeros();
+        if type_bits as usize + size * self.rank_bits() as usize > u128::BITS as usize {
+            return Err(too_long());
+        }
+        Ok(CamelRules {
+            hand_size: size,
+            types,
+            ..self
+        })
+    }
+
+    /// the rules of part 1: plain card ranks, nothing wild
+    pub fn standard() -> Self {
+        CamelRules::new("23456789TJQKA", "").unwrap()
+    }
+
+    /// the rules of part 2: `J` is a joker, which is wild and the weakest card
+    pub fn jokers() -> Self {
+        CamelRules::new("J23456789TQKA", "J").unwrap()
+    }
+
+    pub fn hand_size(&self) -> usize {
+        self.hand_size
+    }
+
+    fn card(&self, label: char) -> Result<Card, String> {
+        let rank = self
+            .order
+            .iter()
+            .position(|&c| c == label)
+            .ok_or_else(|| format!("unknown card {:?}", label))?;
+        Ok(Card {
+            rank: rank as u8,
+            wild: self.wild.contains(&label),
+        })
+    }
+
+    fn label(&self, card: Card) -> char {
+        self.order[card.rank as usize]
+    }
+
+    /// the bits a card's rank takes up in a sort key
+    fn rank_bits(&self) -> u32 {
+        usize::BITS - self.order.len().saturating_sub(1).leading_zeros()
+    }
+
+    /// how many types of a hand of this size are weaker than `hand_type`
+    fn ladder_position(&self, hand_type: &HandType) -> u128 {
+        let (mut position, mut left) = (0, self.hand_size);
+        for &group in &hand_type.groups {
+            // after the same larger groups, every type with a smaller group here is weaker
+            position += (1..group)
+                .map(|smaller| self.types[left - smaller][smaller])
+                .sum::<u128>();
+            left -= group;
+        }
+        position
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Card {
+    rank: u8,
+    wild: bool,
+}
+
+/// The type of a hand: the sizes of its groups of equal cards, largest first, with single cards
+/// as groups of one. Types compare group by group, which for five cards is the usual ladder from
+/// high card (1+1+1+1+1) to five of a kind (5). It works the same for any hand size, so seven of
+/// a kind beats every other seven-card hand and the full houses 4+3, 4+2+1 and 3+3+1 rank by
+/// their largest groups.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandType {
+    groups: Vec<usize>,
+}
+
+impl HandType {
+    /// the type with groups of these sizes, in any order
+    pub fn new(groups: &[usize]) -> Self {
+        let mut groups = groups
+            .iter()
+            .copied()
+            .filter(|&g| g > 0)
+            .collect::<Vec<_>>();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+        HandType { groups }
+    }
+
+    /// the sizes of the groups of equal cards, largest first
+    pub fn groups(&self) -> &[usize] {
+        &self.groups
+    }
+
+    fn of(cards: &[Card]) -> Self {
+        let mut ranks = cards
+            .iter()
+            .filter(|c| !c.wild)
+            .map(|c| c.rank)
+            .collect::<Vec<_>>();
+        ranks.sort_unstable();
+        let groups = ranks
+            .chunk_by(|a, b| a == b)
+            .map(|group| group.len())
+            .collect::<Vec<_>>();
+        HandType::with_wild(&groups, cards.len() - ranks.len())
+    }
+
+    /// the type of a hand with groups of these sizes of cards that are not wild, and `num_wild`
+    /// wild cards
+    fn with_wild(groups: &[usize], num_wild: usize) -> Self {
+        let mut hand_type = HandType::new(groups);
+        // the wild cards all join the largest group
+        match hand_type.groups.first_mut() {
+            Some(largest) => *largest += num_wild,
+            None => hand_type.groups.push(num_wild),
+        }
+        hand_type
+    }
+}
+
+fn number(n: usize) -> String {
+    const WORDS: [&str; 9] = [
+        "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+    match n.checked_sub(2).and_then(|i| WORDS.get(i)) {
+        Some(word) => word.to_string(),
+        None => n.to_string(),
+    }
+}
+
+impl fmt::Display for HandType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let groups = self
+            .groups
+            .iter()
+            .copied()
+            .filter(|&g| g > 1)
+            .collect::<Vec<_>>();
+        match groups.as_slice() {
+            [] => write!(f, "high card"),
+            [2] => write!(f, "one pair"),
+            [n] => write!(f, "{} of a kind", number(*n)),
+            [3, 2] => write!(f, "full house"),
+            pairs if pairs.iter().all(|&g| g == 2) => write!(f, "{} pair", number(pairs.len())),
+            _ => {
+                let shape = groups.iter().map(|g| g.to_string()).collect::<Vec<_>>();
+                write!(f, "full house ({})", shape.join("+"))
+            }
+        }
+    }
+}
+
+/// A hand of cards. It is classified once, when it is read, into a key that orders hands by type
+/// and then card by card, so ranking the bids is a plain integer sort.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand {
+    cards: Vec<Card>,
+    key: u128,
+}
+
+impl Hand {
+    fn new(cards: Vec<Card>, rules: &CamelRules) -> Self {
+        // the type's place on the ladder, above the card ranks, first card highest
+        let position = rules.ladder_position(&HandType::of(&cards));
+        let key = cards
+            .iter()
+            .fold(position, |key, c| key << rules.rank_bits() | c.rank as u128);
+        Hand { cards, key }
+    }
+
+    fn parse(idx: usize, line: &str, value: &str, rules: &CamelRules) -> Result<Self, AocError> {
+        let cards = value
+            .char_indices()
+            .map(|(i, c)| {
+                rules
+                    .card(c)
+                    .map_err(|e| AocError::at(idx, line, &value[i..], e))
+            })
+            .collect::<Result<Vec<Card>, _>>()?;
+        if cards.len() != rules.hand_size {
+            return Err(AocError::at(
+                idx,
+                line,
+                value,
+                format!("expected {} cards, found {}", rules.hand_size, cards.len()),
+            ));
+        }
+        Ok(Hand::new(cards, rules))
+    }
+
+    pub fn hand_type(&self) -> HandType {
+        HandType::of(&self.cards)
+    }
+
+    /// orders hands the way the game ranks them
+    pub fn sort_key(&self) -> u128 {
+        self.key
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+fn parse_line(idx: usize, l: &str, rules: &CamelRules) -> Result<(Hand, usize), AocError> {
+    let (hand, bid) = l
+        .split_once(" ")
+        .ok_or_else(|| AocError::at_line(idx, "expected `<hand> <bid>`"))?;
+    let hand = Hand::parse(idx, l, hand, rules)?;
+    let bid = bid
+        .parse()
+        .map_err(|e| AocError::at(idx, l, bid, format!("invalid bid {:?}: {}", bid, e)))?;
+
+    Ok((hand, bid))
+}
+
+/// reads every `<hand> <bid>` line, ranking the cards by `rules`
+pub fn parse_bids(input: &str, rules: &CamelRules) -> Result<Vec<(Hand, usize)>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_line(idx, line, rules))
+        .collect()
+}
+
+/// every bid multiplied by the rank of its hand, the weakest hand having rank 1
+pub fn total_winnings(bids: &[(Hand, usize)]) -> usize {
+    let mut keys = bids
+        .iter()
+        .map(|(hand, bid)| (hand.key, *bid))
+        .collect::<Vec<_>>();
+    keys.sort_unstable();
+    keys.iter()
+        .enumerate()
+        .map(|(rank, (_, bid))| (rank + 1) * bid)
+        .sum::<usize>()
+}
+
+/// the bids, with the hands read once under each part's card rules
+pub struct Bids {
+    standard: Vec<(Hand, usize)>,
+    jokers: Vec<(Hand, usize)>,
+}
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input = Bids;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(Bids {
+            standard: parse_bids(input, &CamelRules::standard())?,
+            jokers: parse_bids(input, &CamelRules::jokers())?,
+        })
+    }
+
+    fn part1(bids: &Self::Input) -> usize {
+        total_winnings(&bids.standard)
+    }
+
+    fn part2(bids: &Self::Input) -> usize {
+        total_winnings(&bids.jokers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::test_support::every_hand;
+
+    fn hand_type(hand: &str, rules: &CamelRules) -> HandType {
+        Hand::parse(0, hand, hand, rules).unwrap().hand_type()
+    }
+
+    /// the strongest type over every way of replacing the wild cards from `from` on by any card
+    fn brute_force_type(cards: &mut [char], from: usize, rules: &CamelRules) -> HandType {
+        match (from..cards.len()).find(|&i| rules.wild.contains(&cards[i])) {
+            Some(i) => {
+                let wild = cards[i];
+                let best = (0..rules.order.len())
+                    .map(|rank| {
+                        cards[i] = rules.order[rank];
+                        brute_force_type(cards, i + 1, rules)
+                    })
+                    .max()
+                    .unwrap();
+                cards[i] = wild;
+                best
+            }
+            None => {
+                let mut counts = HashMap::<char, usize>::new();
+                for c in cards.iter() {
+                    *counts.entry(*c).or_insert(0) += 1;
+                }
+                HandType::new(&counts.into_values().collect::<Vec<_>>())
+            }
+        }
+    }
+
+    #[test]
+    fn classifies_hands_under_each_rule_set() {
+        let (standard, jokers) = (CamelRules::standard(), CamelRules::jokers());
+        for (hand, expected_standard, expected_jokers) in [
+            ("32T3K", &[2, 1, 1, 1][..], &[2, 1, 1, 1][..]),
+            ("KK677", &[2, 2, 1], &[2, 2, 1]),
+            ("T55J5", &[3, 1, 1], &[4, 1]),
+            ("KTJJT", &[2, 2, 1], &[4, 1]),
+            ("QQQJA", &[3, 1, 1], &[4, 1]),
+            ("JJJJJ", &[5], &[5]),
+            ("2345J", &[1, 1, 1, 1, 1], &[2, 1, 1, 1]),
+            ("2233J", &[2, 2, 1], &[3, 2]),
+        ] {
+            let expected_standard = HandType::new(expected_standard);
+            let expected_jokers = HandType::new(expected_jokers);
+            assert_eq!(hand_type(hand, &standard), expected_standard, "{}", hand);
+            assert_eq!(hand_type(hand, &jokers), expected_jokers, "{}", hand);
+        }
+    }
+
+    #[test]
+    fn ranks_cards_by_the_rules() {
+        let parse = |hand: &str, rules: &CamelRules| Hand::parse(0, hand, hand, rules).unwrap();
+        let (standard, jokers) = (CamelRules::standard(), CamelRules::jokers());
+        // the same type, so the first card decides
+        assert!(parse("JKK23", &standard) < parse("QKK23", &standard));
+        assert!(parse("J2345", &jokers) < parse("22345", &jokers));
+        assert!(parse("JKK23", &jokers) > parse("QKK23", &jokers));
+
+        // a custom variant where twos are wild but still the weakest card
+        let twos = CamelRules::new("23456789TJQKA", "2").unwrap();
+        assert_eq!(hand_type("2KKK3", &twos), HandType::new(&[4, 1]));
+        assert!(parse("23456", &twos) < parse("33456", &twos));
+    }
+
+    #[test]
+    fn sort_keys_order_by_type_then_cards() {
+        for (rules, size) in [
+            (CamelRules::standard(), 5),
+            (CamelRules::jokers(), 5),
+            (CamelRules::new("2345J", "J").unwrap(), 6),
+        ] {
+            let rules = rules.with_hand_size(size).unwrap();
+            let hands = every_hand(&['2', '3', 'J', 'Q', 'A'][..size.min(5)], size)
+                .filter_map(|hand| Hand::parse(0, &hand, &hand, &rules).ok())
+                .map(|hand| (hand.hand_type(), hand))
+                .collect::<Vec<_>>();
+            for (a_type, a) in hands.iter().step_by(31) {
+                for (b_type, b) in &hands {
+                    let expected = (a_type, &a.cards).cmp(&(b_type, &b.cards));
+                    assert_eq!(a.sort_key().cmp(&b.sort_key()), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn matches_trying_every_substitution() {
+        for (order, wild, max_size) in [
+            ("234J", "J", 6),
+            ("A234", "2", 5),
+            ("2345", "25", 5),
+            ("J", "J", 4),
+        ] {
+            for size in 1..=max_size {
+                let rules = CamelRules::new(order, wild)
+                    .and_then(|rules| rules.with_hand_size(size))
+                    .unwrap();
+                for hand in every_hand(&rules.order, size) {
+                    let mut cards = hand.chars().collect::<Vec<_>>();
+                    let expected = brute_force_type(&mut cards, 0, &rules);
+                    assert_eq!(hand_type(&hand, &rules), expected, "{}", hand);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn extends_the_ladder_to_any_hand_size() {
+        let rules = CamelRules::new("23456789TJQKA", "J")
+            .and_then(|rules| rules.with_hand_size(7))
+            .unwrap();
+        let ladder = [
+            "2345678", "2245678", "2244678", "2244668", "2224567", "2224467", "2224466", "2224447",
+            "2222567", "2222557", "2222555", "22222A3", "222222A", "2222222",
+        ];
+        let names = [
+            "high card",
+            "one pair",
+            "two pair",
+            "three pair",
+            "three of a kind",
+            "full house",
+            "full house (3+2+2)",
+            "full house (3+3)",
+            "four of a kind",
+            "full house (4+2)",
+            "full house (4+3)",
+            "five of a kind",
+            "six of a kind",
+            "seven of a kind",
+        ];
+        let parse = |hand: &str| Hand::parse(0, hand, hand, &rules).unwrap();
+        for (hand, name) in ladder.iter().zip(names) {
+            assert_eq!(parse(hand).hand_type().to_string(), name);
+        }
+        for pair in ladder.windows(2) {
+            assert!(parse(pair[0]) < parse(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+        assert_eq!(rules.ladder_position(&parse("2222222").hand_type()), 14);
+        assert_eq!(parse("JJJJJJ3").hand_type(), HandType::new(&[7]));
+    }
+
+    #[test]
+    fn rejects_bad_rules() {
+        assert!(CamelRules::new("23456789TJQKA", "X").is_err());
+        assert!(CamelRules::new("2234", "").is_err());
+        assert!(CamelRules::standard().with_hand_size(0).is_err());
+        assert!(CamelRules::standard().with_hand_size(28).is_ok());
+        assert!(CamelRules::standard().with_hand_size(29).is_err());
+        let one_label = CamelRules::new("A", "").unwrap();
+        assert!(one_label.clone().with_hand_size(100).is_ok());
+        assert!(one_label.clone().with_hand_size(MAX_HAND_SIZE + 1).is_err());
+        assert!(one_label.with_hand_size(2000).is_err());
+        let err = Hand::parse(0, "2345", "2345", &CamelRules::standard()).unwrap_err();
+        assert_eq!(err.message, "expected 5 cards, found 4");
+    }
+}
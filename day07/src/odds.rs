@@ -0,0 +1,356 @@
+//! How likely each hand type is under a set of rules, counted exactly over every possible hand,
+//! and a seeded simulation of tournaments between random hands.
+use std::{collections::BTreeMap, fmt};
+
+use common::rng::Rng;
+
+use crate::{parse_bids, total_winnings, CamelRules, HandType};
+
+/// every type a hand of `size` cards can have, from the weakest to the strongest
+pub fn ladder(size: usize) -> Vec<HandType> {
+    types(size, size)
+}
+
+/// the types of a hand of `size` cards with at most `max_groups` different cards, which keeps
+/// the list short for large hands of a few labels
+fn types(size: usize, max_groups: usize) -> Vec<HandType> {
+    fn split(
+        left: usize,
+        largest: usize,
+        groups_left: usize,
+        groups: &mut Vec<usize>,
+        types: &mut Vec<HandType>,
+    ) {
+        if left == 0 {
+            types.push(HandType::new(groups));
+            return;
+        }
+        if groups_left == 0 {
+            return;
+        }
+        for group in 1..=largest.min(left) {
+            groups.push(group);
+            split(left - group, group, groups_left - 1, groups, types);
+            groups.pop();
+        }
+    }
+    let mut types = vec![];
+    split(size, size, max_groups, &mut vec![], &mut types);
+    types.sort();
+    types
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+fn binomial(n: usize, k: usize) -> u128 {
+    (0..k).fold(1, |acc, i| {
+        // every partial product is itself a binomial coefficient, so `i + 1` divides
+        // `acc * (n - i)`; dividing out the common factor first keeps every step below the result
+        let (top, bottom) = ((n - i) as u128, i as u128 + 1);
+        let g = gcd(acc, bottom);
+        acc / g * (top / (bottom / g))
+    })
+}
+
+/// How many of the possible hands have each type. Every type that has at most as many different
+/// cards as there are labels is listed, even the ones no hand has.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distribution {
+    counts: BTreeMap<HandType, u128>,
+}
+
+impl Distribution {
+    /// Counts the hands by how many of each card they hold rather than one by one: a hand with
+    /// `c1` of the first card, `c2` of the second and so on has the same type in any order, and
+    /// there are `size! / (c1! * c2! * ...)` such orders.
+    ///
+    /// No count can overflow: there are `labels^size` hands in total, and the rules only allow
+    /// hand sizes whose sort keys, which tell every hand apart, fit in a `u128`.
+    pub fn exact(rules: &CamelRules) -> Self {
+        let mut counts = types(rules.hand_size(), rules.order.len())
+            .into_iter()
+            .map(|hand_type| (hand_type, 0))
+            .collect::<BTreeMap<_, _>>();
+        let wild = rules
+            .order
+            .iter()
+            .map(|c| rules.wild.contains(c))
+            .collect::<Vec<_>>();
+        count_hands(&wild, rules.hand_size(), 0, 1, &mut vec![], &mut counts);
+        Distribution { counts }
+    }
+
+    /// the number of hands of each type, from the weakest type to the strongest
+    pub fn counts(&self) -> impl Iterator<Item = (&HandType, u128)> {
+        self.counts
+            .iter()
+            .map(|(hand_type, &count)| (hand_type, count))
+    }
+
+    pub fn total(&self) -> u128 {
+        self.counts.values().sum()
+    }
+
+    pub fn count(&self, hand_type: &HandType) -> u128 {
+        self.counts.get(hand_type).copied().unwrap_or(0)
+    }
+
+    pub fn probability(&self, hand_type: &HandType) -> f64 {
+        self.count(hand_type) as f64 / self.total() as f64
+    }
+}
+
+/// Deals the `left` cards still to be placed among the cards in `wild` (whether each is wild),
+/// `ways` being the number of orders of the cards placed so far.
+fn count_hands(
+    wild: &[bool],
+    left: usize,
+    num_wild: usize,
+    ways: u128,
+    groups: &mut Vec<usize>,
+    counts: &mut BTreeMap<HandType, u128>,
+) {
+    let Some((&is_wild, rest)) = wild.split_first() else {
+        if left == 0 {
+            *counts
+                .entry(HandType::with_wild(groups, num_wild))
+                .or_insert(0) += ways;
+        }
+        return;
+    };
+    // the last card takes whatever is left
+    let fewest = match rest.is_empty() {
+        true => left,
+        false => 0,
+    };
+    for n in fewest..=left {
+        let ways = ways * binomial(left, n);
+        match (is_wild, n) {
+            (true, _) => count_hands(rest, left - n, num_wild + n, ways, groups, counts),
+            (false, 0) => count_hands(rest, left, num_wild, ways, groups, counts),
+            (false, _) => {
+                groups.push(n);
+                count_hands(rest, left - n, num_wild, ways, groups, counts);
+                groups.pop();
+            }
+        }
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = self
+            .counts
+            .keys()
+            .map(|hand_type| hand_type.to_string())
+            .collect::<Vec<_>>();
+        let width = names.iter().map(|n| n.len()).max().unwrap_or(0);
+        for (name, (hand_type, count)) in names.iter().zip(self.counts()) {
+            writeln!(
+                f,
+                "{:<width$}  {:>10}  {:>8.4}%",
+                name,
+                count,
+                self.probability(hand_type) * 100.0,
+                width = width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Random tournaments: every round deals `hands` random hands, with bids from 1 to `max_bid`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tournament {
+    pub hands: usize,
+    pub max_bid: usize,
+    pub rounds: usize,
+    pub seed: u64,
+}
+
+/// the total winnings over the rounds of a tournament
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Simulation {
+    pub rounds: usize,
+    pub mean: f64,
+    pub std_dev: f64,
+    /// the standard deviation of the mean, which shrinks as the rounds grow
+    pub std_error: f64,
+    pub min: usize,
+    pub max: usize,
+}
+
+impl Tournament {
+    /// each round's bid file, in the puzzle's format, the same ones for the same seed; without
+    /// any labels no hand can be dealt, so there are no rounds
+    pub fn bid_files<'a>(&self, rules: &'a CamelRules) -> impl Iterator<Item = String> + 'a {
+        let Tournament { hands, max_bid, .. } = *self;
+        let mut rng = Rng::new(self.seed);
+        let rounds = match rules.order.is_empty() {
+            true => 0,
+            false => self.rounds,
+        };
+        (0..rounds).map(move |_| {
+            let mut file = String::new();
+            for _ in 0..hands {
+                for _ in 0..rules.hand_size() {
+                    file.push(rules.order[rng.below(rules.order.len())]);
+                }
+                file.push_str(&format!(" {}\n", rng.below(max_bid.max(1)) + 1));
+            }
+            file
+        })
+    }
+
+    pub fn simulate(&self, rules: &CamelRules) -> Simulation {
+        let winnings = self
+            .bid_files(rules)
+            .map(|file| {
+                let bids = parse_bids(&file, rules).expect("dealt bid files are valid");
+                total_winnings(&bids)
+            })
+            .collect::<Vec<_>>();
+        let rounds = winnings.len().max(1) as f64;
+        let mean = winnings.iter().map(|&w| w as f64).sum::<f64>() / rounds;
+        let variance = winnings
+            .iter()
+            .map(|&w| (w as f64 - mean).powi(2))
+            .sum::<f64>()
+            / rounds;
+        Simulation {
+            rounds: winnings.len(),
+            mean,
+            std_dev: variance.sqrt(),
+            std_error: variance.sqrt() / rounds.sqrt(),
+            min: winnings.iter().copied().min().unwrap_or(0),
+            max: winnings.iter().copied().max().unwrap_or(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_support::every_hand, Hand};
+
+    /// the distribution found by classifying every hand one at a time
+    fn count_every_hand(rules: &CamelRules) -> Distribution {
+        let mut counts = ladder(rules.hand_size())
+            .into_iter()
+            .filter(|hand_type| hand_type.groups.len() <= rules.order.len())
+            .map(|hand_type| (hand_type, 0))
+            .collect::<BTreeMap<_, _>>();
+        for hand in every_hand(&rules.order, rules.hand_size()) {
+            let hand_type = Hand::parse(0, &hand, &hand, rules).unwrap().hand_type();
+            *counts.get_mut(&hand_type).unwrap() += 1;
+        }
+        Distribution { counts }
+    }
+
+    #[test]
+    fn counts_every_standard_hand() {
+        let distribution = Distribution::exact(&CamelRules::standard());
+        let counts = distribution.counts().map(|(_, c)| c).collect::<Vec<_>>();
+        assert_eq!(counts, [154440, 171600, 25740, 17160, 1560, 780, 13]);
+        assert_eq!(distribution.total(), 13u128.pow(5));
+
+        let jokers = Distribution::exact(&CamelRules::jokers());
+        assert_eq!(jokers.total(), 13u128.pow(5));
+        let high_card = HandType::new(&[1, 1, 1, 1, 1]);
+        assert_eq!(jokers.count(&high_card), 12 * 11 * 10 * 9 * 8);
+        let five = HandType::new(&[5]);
+        assert_eq!(jokers.count(&five), 13 + 12 * (5 + 10 + 10 + 5));
+    }
+
+    #[test]
+    fn matches_classifying_every_hand() {
+        for (order, wild, sizes) in [
+            ("23456789TJQKA", "", 1..=4),
+            ("J23456789TQKA", "J", 1..=4),
+            ("2345J", "J", 1..=6),
+            ("A234", "A2", 1..=6),
+            ("J", "J", 1..=3),
+        ] {
+            for size in sizes {
+                let rules = CamelRules::new(order, wild)
+                    .and_then(|rules| rules.with_hand_size(size))
+                    .unwrap();
+                assert_eq!(Distribution::exact(&rules), count_every_hand(&rules));
+            }
+        }
+    }
+
+    #[test]
+    fn counts_large_hands_of_few_labels() {
+        assert_eq!(binomial(128, 64), 23951146041928082866135587776380551750);
+        assert_eq!(binomial(128, 0), 1);
+
+        let rules = CamelRules::new("J", "")
+            .and_then(|rules| rules.with_hand_size(128))
+            .unwrap();
+        let distribution = Distribution::exact(&rules);
+        assert_eq!(distribution.counts().count(), 1);
+        assert_eq!(distribution.count(&HandType::new(&[128])), 1);
+
+        let rules = CamelRules::new("AJ", "J")
+            .and_then(|rules| rules.with_hand_size(100))
+            .unwrap();
+        let distribution = Distribution::exact(&rules);
+        assert_eq!(distribution.total(), 2u128.pow(100));
+        // every hand has jokers count as aces
+        assert_eq!(distribution.count(&HandType::new(&[100])), 2u128.pow(100));
+    }
+
+    #[test]
+    fn ladders_agree_with_sort_keys() {
+        for size in 1..=8 {
+            let rules = CamelRules::standard().with_hand_size(size).unwrap();
+            let ladder = ladder(size);
+            assert_eq!(ladder.len() as u128, rules.types[size][size]);
+            for (position, hand_type) in ladder.iter().enumerate() {
+                assert_eq!(rules.ladder_position(hand_type), position as u128);
+            }
+        }
+    }
+
+    #[test]
+    fn simulates_the_expected_winnings() {
+        let tournament = Tournament {
+            hands: 100,
+            max_bid: 1000,
+            rounds: 1000,
+            seed: 7,
+        };
+        let rules = CamelRules::jokers();
+        let simulation = tournament.simulate(&rules);
+        assert_eq!(simulation, tournament.simulate(&rules));
+        assert_ne!(
+            simulation,
+            Tournament {
+                seed: 8,
+                ..tournament
+            }
+            .simulate(&rules)
+        );
+
+        // bids do not depend on the hands, so every rank is worth the average bid
+        let expected = 500.5 * (100.0 * 101.0 / 2.0);
+        assert!((simulation.mean - expected).abs() < 4.0 * simulation.std_error);
+        assert!(
+            simulation.min as f64 <= simulation.mean && simulation.mean <= simulation.max as f64
+        );
+
+        let file = tournament.bid_files(&rules).next().unwrap();
+        assert_eq!(file.lines().count(), 100);
+        assert!(parse_bids(&file, &rules).is_ok());
+
+        let no_labels = CamelRules::new("", "").unwrap();
+        assert_eq!(tournament.bid_files(&no_labels).count(), 0);
+        assert_eq!(tournament.simulate(&no_labels).rounds, 0);
+    }
+}
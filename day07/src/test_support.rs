@@ -0,0 +1,14 @@
+//! Helpers shared by the tests of several modules.
+
+/// every hand of `size` cards made of `labels`
+pub fn every_hand(labels: &[char], size: usize) -> impl Iterator<Item = String> + '_ {
+    (0..labels.len().pow(size as u32)).map(move |mut n| {
+        (0..size)
+            .map(|_| {
+                let c = labels[n % labels.len()];
+                n /= labels.len();
+                c
+            })
+            .collect()
+    })
+}
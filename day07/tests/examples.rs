@@ -0,0 +1,3 @@
+use day07::Day07;
+
+common::example_tests!(Day07: part1, part2);
@@ -0,0 +1,159 @@
+use common::{AocError, Solution};
+use regex::Regex;
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+pub struct Day08;
+
+type Instructions = HashMap<String, [String; 2]>;
+
+#[derive(Debug)]
+pub struct Network {
+    step_list: Vec<usize>,
+    instructions: Instructions,
+}
+
+impl FromStr for Network {
+    type Err = AocError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut lines = value.split("\n").enumerate().filter(|(_, l)| !l.is_empty());
+        let (idx, steps) = lines
+            .next()
+            .ok_or_else(|| AocError::new("expected a line of L/R steps"))?;
+        let step_list = steps
+            .char_indices()
+            .map(|(i, c)| match c {
+                'L' => Ok(0),
+                'R' => Ok(1),
+                _ => Err(AocError::at(
+                    idx,
+                    steps,
+                    &steps[i..],
+                    format!("unknown step {:?}", c),
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+        let re = Regex::new(r"^(?<from>[^ ]+) = \((?<left>.*), (?<right>.*)\)$").unwrap();
+        let nodes = lines
+            .map(|(idx, line)| {
+                re.captures(line)
+                    .map(|cap| (idx, line, cap))
+                    .ok_or_else(|| AocError::at_line(idx, "expected `<node> = (<left>, <right>)`"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let instructions: Instructions = nodes
+            .iter()
+            .map(|(_, _, cap)| {
+                (
+                    cap["from"].into(),
+                    [cap["left"].into(), cap["right"].into()],
+                )
+            })
+            .collect();
+
+        // every node we can step to has to be defined, so walking the network can't get stuck
+        for (idx, line, cap) in nodes.iter() {
+            for next in [&cap["left"], &cap["right"]] {
+                if !instructions.contains_key(next) {
+                    return Err(AocError::at(
+                        *idx,
+                        line,
+                        next,
+                        format!("node {} is not defined", next),
+                    ));
+                }
+            }
+        }
+
+        // part 1 walks from AAA to ZZZ
+        let last_idx = nodes.last().map_or(idx, |(node_idx, _, _)| *node_idx);
+        for node in ["AAA", "ZZZ"] {
+            if !instructions.contains_key(node) {
+                return Err(AocError::at_line(
+                    last_idx,
+                    format!("the network has no node {}", node),
+                ));
+            }
+        }
+
+        Ok(Network {
+            step_list,
+            instructions,
+        })
+    }
+}
+
+fn get_factors_functional(n: usize) -> Vec<usize> {
+    (2..n)
+        .filter(|&x| n.is_multiple_of(x))
+        .collect::<Vec<usize>>()
+}
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input = Network;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        input.parse()
+    }
+
+    fn part1(network: &Self::Input) -> usize {
+        let mut curr_node = "AAA".to_string();
+
+        let mut step_list = network.step_list.iter().cycle();
+        let mut num_steps = 0;
+        while curr_node != "ZZZ" {
+            let step = step_list.next().unwrap();
+            curr_node = network.instructions[&curr_node][*step].clone();
+            num_steps += 1;
+        }
+        num_steps
+    }
+
+    fn part2(network: &Self::Input) -> usize {
+        let curr_nodes = network
+            .instructions
+            .keys()
+            .filter(|n| n.ends_with("A"))
+            .cloned()
+            .collect::<Vec<String>>();
+
+        let mut num_steps_list = Vec::<_>::new();
+
+        for start_node in curr_nodes.iter() {
+            let mut curr_node = start_node.clone();
+            let mut step_list = network.step_list.iter().cycle();
+            let mut num_steps = 0;
+            while !curr_node.ends_with("Z") {
+                let step = step_list.next().unwrap();
+                curr_node = network.instructions[&curr_node][*step].clone();
+                num_steps += 1;
+            }
+            num_steps_list.push(num_steps);
+        }
+        let factors_set = num_steps_list
+            .iter()
+            .flat_map(|n| get_factors_functional(*n))
+            .collect::<HashSet<_>>();
+        factors_set.iter().product::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_networks_without_start_or_end() {
+        let err = Day08::parse("LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)").unwrap_err();
+        assert_eq!(err.to_string(), "4: the network has no node ZZZ");
+        let err = Day08::parse("L\n\nZZZ = (ZZZ, ZZZ)").unwrap_err();
+        assert_eq!(err.to_string(), "3: the network has no node AAA");
+    }
+}
@@ -0,0 +1,3 @@
+use day08::Day08;
+
+common::example_tests!(Day08: part1 = 1);
@@ -0,0 +1,88 @@
+use std::iter::zip;
+
+use common::{parse, AocError, Solution};
+
+pub struct Day09;
+
+fn derive(values: Vec<i64>) -> Vec<i64> {
+    let first = values.iter();
+    let second = values.iter().skip(1);
+    zip(first, second).map(|(a, b)| b - a).collect()
+}
+
+fn extrapolate(in_values: &[i64]) -> i64 {
+    let mut values = in_values.to_vec();
+    let mut last_vals = vec![*values.last().unwrap()];
+    while values.iter().any(|x| *x != values[0]) {
+        values = derive(values);
+        last_vals.push(*values.last().unwrap());
+    }
+
+    // values is now a constant value
+    last_vals.iter().sum::<i64>()
+}
+
+fn extrapolate_back(in_values: &[i64]) -> i64 {
+    let mut values = in_values.to_vec();
+    let mut first_values = vec![values[0]];
+    while values.iter().any(|x| *x != values[0]) {
+        values = derive(values);
+        first_values.push(values[0]);
+    }
+
+    // now run on the first values to get the extrapolated ones:
+    let mut last_derived = 0;
+    first_values.reverse();
+    for v in first_values.iter() {
+        last_derived = v - last_derived;
+    }
+
+    last_derived
+}
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Vec<i64>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                let values = parse::integers::<i64>(line)
+                    .map(parse::values)
+                    .map_err(|e| e.on_line(idx, line))?;
+                match values.is_empty() {
+                    true => Err(AocError::at_line(idx, "expected a history of numbers")),
+                    false => Ok(values),
+                }
+            })
+            .collect()
+    }
+
+    fn part1(histories: &Self::Input) -> i64 {
+        histories.iter().map(|values| extrapolate(values)).sum()
+    }
+
+    fn part2(histories: &Self::Input) -> i64 {
+        histories
+            .iter()
+            .map(|values| extrapolate_back(values))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_empty_histories() {
+        let err = Day09::parse("0 3 6 9 12 15\n\n10 13 16 21 30 45").unwrap_err();
+        assert_eq!(err.line, Some(2));
+        assert_eq!(err.to_string(), "2: expected a history of numbers");
+    }
+}
@@ -0,0 +1,3 @@
+use day09::Day09;
+
+common::example_tests!(Day09: part1, part2);